@@ -0,0 +1,178 @@
+use super::{
+    client::{self, RECONNECT_MAX_DELAY, RECONNECT_MIN_DELAY},
+    types::{ClientInfo, SubscriptionID},
+};
+use crate::{
+    config::config,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    opts::{BarOpts, ClickCommand},
+    output::{self, i3bar, waybar, Template},
+};
+
+use log::LevelFilter;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    process::Stdio,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    sync::mpsc::{self, UnboundedSender},
+    time::sleep,
+};
+
+pub async fn start_bar(opts: BarOpts, filter: LevelFilter) -> HyprvisorResult<()> {
+    // Stdout belongs to the bar, keep debug logs on stderr.
+    client::init_logger(filter, io::stderr().into())?;
+
+    let templates = opts
+        .formats
+        .iter()
+        .map(|format| Ok((format.subscription_id, Template::parse(&format.template)?)))
+        .collect::<HyprvisorResult<HashMap<_, _>>>()?;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+
+    for (index, &subscription_id) in opts.topics.iter().enumerate() {
+        tokio::spawn(follow_topic(
+            index,
+            subscription_id,
+            opts.spawn_daemon || config().client.spawn_daemon,
+            sender.clone(),
+        ));
+    }
+
+    tokio::spawn(handle_click_events(opts.on_click.clone()));
+
+    let mut stdout = io::stdout();
+    writeln!(stdout, "{}", i3bar::HEADER)?;
+    writeln!(stdout, "[")?;
+    stdout.flush()?;
+
+    let mut texts = vec![None; opts.topics.len()];
+    let mut separator = "";
+
+    while let Some((index, value)) = receiver.recv().await {
+        let subscription_id = opts.topics[index];
+
+        let text = match templates.get(&subscription_id) {
+            Some(template) => template.render(&output::localized(subscription_id, &value)),
+            None => match waybar::text(subscription_id, &value) {
+                Ok(text) => text,
+                Err(e) => {
+                    log::warn!("Skip an update of {subscription_id}: {e}");
+                    continue;
+                }
+            },
+        };
+        texts[index] = Some(text);
+
+        writeln!(
+            stdout,
+            "{separator}{}",
+            i3bar::status_line(&opts.topics, &texts)?
+        )?;
+        stdout.flush()?;
+
+        separator = ",";
+    }
+
+    Ok(())
+}
+
+/// Keep one subscription alive for a block, reconnecting with backoff.
+/// A daemon stopped on purpose is waited for, never spawned again.
+async fn follow_topic(
+    index: usize,
+    subscription_id: SubscriptionID,
+    spawn_daemon: bool,
+    sender: UnboundedSender<(usize, Value)>,
+) {
+    let client_info =
+        ClientInfo::with_options(subscription_id, client::default_options(subscription_id));
+    let mut delay = RECONNECT_MIN_DELAY;
+    let mut spawn = spawn_daemon;
+
+    loop {
+        let stream = match client::ensure_daemon(spawn).await {
+            Ok(_) => client::subscribe(&client_info).await,
+            Err(e) => Err(e),
+        };
+
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("Failed to subscribe to {subscription_id}: {e}. Retry in {delay}ms.");
+                sleep(Duration::from_millis(delay)).await;
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                continue;
+            }
+        };
+
+        spawn = spawn_daemon;
+
+        let result = client::read_messages(&stream, subscription_id, |value| {
+            delay = RECONNECT_MIN_DELAY;
+            sender
+                .send((index, value))
+                .map_err(|_| HyprvisorError::Client(ClientError::IpcError))
+        })
+        .await;
+
+        match result {
+            Ok(_) => {
+                log::info!("Wait for the server to come back.");
+                spawn = false;
+            }
+            Err(e) => log::warn!("Lost {subscription_id}: {e}. Reconnecting in {delay}ms."),
+        }
+
+        sleep(Duration::from_millis(delay)).await;
+        delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+    }
+}
+
+async fn handle_click_events(commands: Vec<ClickCommand>) -> HyprvisorResult<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let Some(event) = i3bar::parse_click_event(&line) else {
+            continue;
+        };
+
+        let find_command = |button: Option<u8>| {
+            commands.iter().find(|command| {
+                event.name.as_deref() == Some(&i3bar::block_name(command.subscription_id))
+                    && command.button == button
+            })
+        };
+
+        if let Some(command) = find_command(event.button).or_else(|| find_command(None)) {
+            run_command(&command.command);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_command(command: &str) {
+    log::info!("Run: {command}");
+
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            tokio::spawn(async move { child.wait().await });
+        }
+        Err(e) => log::error!("Failed to run {command}. Error: {e}"),
+    }
+}
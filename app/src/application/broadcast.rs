@@ -0,0 +1,270 @@
+use super::{
+    format, status,
+    types::{ClientInfo, Connection, PushOutcome, SendQueue, SubscriptionID},
+};
+use crate::{
+    config::config,
+    error::{HyprvisorError, HyprvisorResult},
+    global::{RATE_LIMITERS, SUBSCRIBERS, TOPICS},
+    opts::{DaemonOpts, OverflowPolicy, RateLimit, RateLimitWindow},
+};
+
+use hyprvisor_client::ipc::{
+    message::{HyprvisorMessage, MessageType},
+    HyprvisorWriteSock,
+};
+use std::{io::ErrorKind, time::Duration};
+use tokio::{
+    net::unix::{OwnedReadHalf, OwnedWriteHalf},
+    sync::{
+        broadcast::{error::RecvError, Receiver},
+        mpsc::{self, UnboundedReceiver},
+        Notify,
+    },
+    time::{sleep, sleep_until, Instant},
+};
+
+pub fn publish(subscription_id: SubscriptionID, message: HyprvisorMessage) -> HyprvisorResult<()> {
+    match RATE_LIMITERS
+        .get()
+        .and_then(|limiters| limiters.get(&subscription_id))
+    {
+        Some(limiter) => limiter
+            .send(message)
+            .map_err(|_| HyprvisorError::NoSubscriber),
+        None => deliver(subscription_id, message),
+    }
+}
+
+fn deliver(subscription_id: SubscriptionID, message: HyprvisorMessage) -> HyprvisorResult<()> {
+    status::record_update(subscription_id);
+
+    TOPICS[&subscription_id]
+        .send(message)
+        .map(|_| ())
+        .map_err(|_| HyprvisorError::NoSubscriber)
+}
+
+/// Route every topic through its own limiter task. The window of a topic comes from
+/// `--rate-limit`, or else from `[rate-limit]` in the config.
+pub fn start_rate_limiters(rate_limits: &[RateLimit]) {
+    let limiters = [
+        SubscriptionID::Workspaces,
+        SubscriptionID::Window,
+        SubscriptionID::Wifi,
+        SubscriptionID::Bluetooth,
+    ]
+    .into_iter()
+    .map(|subscription_id| {
+        let window = rate_limits
+            .iter()
+            .rev()
+            .find(|rate_limit| rate_limit.subscription_id == subscription_id)
+            .map(|rate_limit| rate_limit.window);
+
+        if let Some(window) = window {
+            log::info!(
+                "Rate limit {subscription_id}: {}ms, {} edge",
+                window.interval,
+                window.edge
+            );
+        }
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(rate_limit_topic(subscription_id, window, receiver));
+        (subscription_id, sender)
+    })
+    .collect();
+
+    let _ = RATE_LIMITERS.set(limiters);
+}
+
+/// A burst starts with the first message after a quiet period and opens a window
+/// of one interval. On the leading edge that message goes out right away. When the
+/// window ends, the latest state not sent yet goes out and a new window opens, so
+/// a burst never loses its final state and the topic updates at most once per
+/// interval. The window is read at every burst, so a config reload applies to the next one.
+async fn rate_limit_topic(
+    subscription_id: SubscriptionID,
+    cli_window: Option<RateLimitWindow>,
+    mut receiver: UnboundedReceiver<HyprvisorMessage>,
+) {
+    while let Some(message) = receiver.recv().await {
+        let Some(window) = cli_window.or_else(|| config().rate_limit.get(subscription_id)) else {
+            let _ = deliver(subscription_id, message);
+            continue;
+        };
+
+        let interval = Duration::from_millis(window.interval);
+        let mut pending = None;
+
+        match window.edge.leading() {
+            true => {
+                let _ = deliver(subscription_id, message);
+            }
+            false => pending = Some(message),
+        }
+
+        let mut deadline = Instant::now() + interval;
+
+        loop {
+            tokio::select! {
+                _ = sleep_until(deadline) => {
+                    let Some(message) = pending.take() else {
+                        break;
+                    };
+
+                    let _ = deliver(subscription_id, message);
+                    deadline = Instant::now() + interval;
+                }
+                message = receiver.recv() => {
+                    let Some(message) = message else {
+                        return;
+                    };
+
+                    pending = Some(message);
+                }
+            }
+        }
+    }
+}
+
+pub fn subscribe(subscription_id: SubscriptionID) -> Receiver<HyprvisorMessage> {
+    TOPICS[&subscription_id].subscribe()
+}
+
+pub async fn serve_subscriber(
+    connection: Connection,
+    client_info: ClientInfo,
+    mut receiver: Receiver<HyprvisorMessage>,
+    reader: OwnedReadHalf,
+    writer: OwnedWriteHalf,
+    opts: &DaemonOpts,
+) {
+    let subscription_id = client_info.subscription_id;
+    let min_interval = Duration::from_millis(client_info.options.min_interval.into());
+
+    // A throttled client only needs the latest state once its next slot comes.
+    let queue = match min_interval.is_zero() {
+        true => SendQueue::new(
+            opts.queue_size.unwrap_or(config().daemon.queue_size),
+            opts.overflow_policy
+                .unwrap_or(config().daemon.overflow_policy),
+        ),
+        false => SendQueue::new(1, OverflowPolicy::Coalesce),
+    };
+
+    let mut slow_pushes: usize = 0;
+    let stopping = Notify::new();
+
+    let feed_queue = async {
+        loop {
+            let message = match receiver.recv().await {
+                Ok(message) => message,
+                Err(RecvError::Lagged(count)) => {
+                    log::warn!(
+                        "{connection} lagged behind {subscription_id}, skipped {count} messages."
+                    );
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            // The daemon is stopping, the client must hear it before the timeout
+            // whatever its queue and interval.
+            if message.message_type == MessageType::Shutdown {
+                queue.push_last(message);
+                stopping.notify_one();
+                continue;
+            }
+
+            let action = match queue.push(message) {
+                PushOutcome::Queued => continue,
+                PushOutcome::Coalesced if !min_interval.is_zero() => continue,
+                PushOutcome::DroppedOldest => "dropped oldest queued message",
+                PushOutcome::Coalesced => "coalesced queued messages to latest",
+                PushOutcome::Overflowed => {
+                    log::warn!("{connection} is slow, send queue is full.");
+                    return;
+                }
+            };
+
+            // Warn once, a slow client would flood the log otherwise.
+            slow_pushes += 1;
+            match slow_pushes {
+                1 => log::warn!("{connection} is slow, {action}."),
+                _ => log::debug!("{connection} is slow, {action}."),
+            }
+        }
+    };
+
+    let drain_queue = async {
+        loop {
+            let message = match format::format_message(&client_info, queue.pop().await) {
+                Ok(message) => message,
+                Err(e) => {
+                    log::warn!("Failed to format message for {connection}. Error: {e}");
+                    continue;
+                }
+            };
+
+            if writer.try_write_message(&message, 2).await.is_err() {
+                log::debug!("{connection} is disconnected.");
+                return;
+            }
+
+            if message.message_type == MessageType::Shutdown {
+                return;
+            }
+
+            if !min_interval.is_zero() {
+                tokio::select! {
+                    _ = sleep(min_interval) => {}
+                    _ = stopping.notified() => {}
+                }
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = feed_queue => {}
+        _ = drain_queue => {}
+        _ = wait_for_hangup(&reader) => {
+            log::info!("{connection} hung up.");
+        }
+    }
+
+    if slow_pushes > 1 {
+        log::info!("{connection} was too slow for {slow_pushes} messages.");
+    }
+
+    let mut subscribers_ref = SUBSCRIBERS.lock().await;
+    if let Some(subscribers) = subscribers_ref.get_mut(&subscription_id) {
+        if let Some(subscriber) = subscribers.remove(&connection.id) {
+            log::info!(
+                "Remove client {} ({}). {} subscriber(s) left on {subscription_id}.",
+                connection.id,
+                subscriber.peer,
+                subscribers.len()
+            );
+        }
+    }
+}
+
+/// Resolves once the peer closes its end of the socket.
+async fn wait_for_hangup(reader: &OwnedReadHalf) {
+    let mut buffer = [0; 64];
+
+    loop {
+        if reader.readable().await.is_err() {
+            return;
+        }
+
+        match reader.try_read(&mut buffer) {
+            Ok(0) => return,
+            Ok(len) => log::debug!("Ignore {len} bytes from subscriber."),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+            Err(_) => return,
+        }
+    }
+}
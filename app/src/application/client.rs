@@ -1,45 +1,165 @@
 use super::{
-    types::{ClientInfo, SubscriptionID},
-    utils::{ping_daemon, HYPRVISOR_SOCKET},
+    types::{ClientInfo, SubscriptionID, SubscriptionOptions},
+    utils::{ping_daemon, spawn_daemon, HYPRVISOR_SOCKET},
 };
 use crate::{
     bluetooth::types::BluetoothInfo,
-    error::{HyprvisorError, HyprvisorResult},
-    hyprland::types::{FormattedInfo, HyprWindowInfo, HyprWorkspaceInfo},
-    ipc::{connect_to_socket, message::HyprvisorMessage, HyprvisorReadSock, HyprvisorWriteSock},
-    opts::SubscribeOpts,
+    config::config,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    hyprland::types::{HyprWindowInfo, HyprWorkspaceInfo},
+    opts::{QueryOpts, SubscribeOpts},
+    output::Output,
     wifi::types::WifiInfo,
 };
 
+use clap::Args;
 use humantime::format_rfc3339_seconds;
+use hyprvisor_client::ipc::{
+    connect_to_socket,
+    message::{HyprvisorMessage, MessageType},
+    HyprvisorReadSock, HyprvisorRequestResponse, HyprvisorWriteSock,
+};
 use log::LevelFilter;
-use std::{process, time::SystemTime};
-use tokio::net::UnixStream;
+use serde_json::Value;
+use std::{
+    process,
+    time::{Duration, SystemTime},
+};
+use tokio::{net::UnixStream, time::sleep};
+
+pub(super) const RECONNECT_MIN_DELAY: u64 = 100;
+pub(super) const RECONNECT_MAX_DELAY: u64 = 5000;
 
 pub async fn start_client(opts: SubscribeOpts, filter: LevelFilter) -> HyprvisorResult<()> {
-    init_logger(filter)?;
-    ping_daemon().await?;
+    init_logger(filter, std::io::stdout().into())?;
 
-    let (subscription_id, extra_data) = parse_opts(opts);
-    let stream = subscribe(subscription_id).await?;
+    let listen_opts = opts.opts().clone();
+    let spawn_daemon = listen_opts.query.spawn_daemon || config().client.spawn_daemon;
+    let client_info = parse_opts(&opts, listen_opts.min_interval);
+    let output = Output::new(&opts)?;
+
+    if listen_opts.no_reconnect {
+        ensure_daemon(spawn_daemon).await?;
+        let stream = subscribe(&client_info).await?;
+        return print_messages(&stream, client_info.subscription_id, &output).await;
+    }
+
+    let mut delay = RECONNECT_MIN_DELAY;
+    let mut placeholder_shown = false;
+    let mut spawn = spawn_daemon;
 
+    loop {
+        let stream = match ensure_daemon(spawn).await {
+            Ok(_) => subscribe(&client_info).await,
+            Err(e) => Err(e),
+        };
+
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("Failed to subscribe: {e}. Retry in {delay}ms.");
+
+                if !placeholder_shown {
+                    if let Some(placeholder) = &listen_opts.placeholder {
+                        println!("{placeholder}");
+                    }
+                    placeholder_shown = true;
+                }
+
+                sleep(Duration::from_millis(delay)).await;
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                continue;
+            }
+        };
+
+        delay = RECONNECT_MIN_DELAY;
+        placeholder_shown = false;
+        spawn = spawn_daemon;
+
+        match print_messages(&stream, client_info.subscription_id, &output).await {
+            Ok(_) if listen_opts.exit_on_shutdown => return Ok(()),
+            // A daemon stopped on purpose is restarted by whoever stopped it, not spawned again.
+            Ok(_) => {
+                log::info!("Wait for the server to come back.");
+                spawn = false;
+            }
+            Err(e) => log::warn!("Lost connection to server: {e}. Reconnecting..."),
+        }
+    }
+}
+
+pub(super) async fn ensure_daemon(spawn: bool) -> HyprvisorResult<()> {
+    match ping_daemon().await {
+        Err(HyprvisorError::Client(ClientError::NoDaemon)) if spawn => spawn_daemon().await,
+        result => result,
+    }
+}
+
+async fn print_messages(
+    stream: &UnixStream,
+    subscription_id: SubscriptionID,
+    output: &Output,
+) -> HyprvisorResult<()> {
+    read_messages(stream, subscription_id, |value| {
+        println!("{}", output.render(subscription_id, &value)?);
+        Ok(())
+    })
+    .await
+}
+
+/// Hand every update of a subscription to `on_value` until the server stops.
+/// `Ok` means the server shut down on purpose.
+pub(super) async fn read_messages(
+    stream: &UnixStream,
+    subscription_id: SubscriptionID,
+    mut on_value: impl FnMut(Value) -> HyprvisorResult<()>,
+) -> HyprvisorResult<()> {
     loop {
         let response_message = match stream.try_read_message(3).await {
             Ok(message) => message,
             Err(e) => {
                 log::error!("Failed to read message from server: {e}");
-                return Err(e);
+                return Err(e.into());
             }
         };
 
-        println!(
-            "{}",
-            parse_response(response_message, subscription_id, &extra_data)?
-        );
+        if response_message.message_type == MessageType::Shutdown {
+            log::info!(
+                "Server stopped: {}",
+                String::from_utf8_lossy(&response_message.payload)
+            );
+            return Ok(());
+        }
+
+        on_value(parse_response(response_message, subscription_id)?)?;
     }
 }
 
-fn init_logger(filter: LevelFilter) -> HyprvisorResult<()> {
+pub async fn query(opts: SubscribeOpts<QueryOpts>, filter: LevelFilter) -> HyprvisorResult<()> {
+    init_logger(filter, std::io::stdout().into())?;
+    ensure_daemon(opts.opts().spawn_daemon || config().client.spawn_daemon).await?;
+
+    let client_info = parse_opts(&opts, 0);
+    let output = Output::new(&opts)?;
+
+    let stream = connect_to_socket(&HYPRVISOR_SOCKET, 1, 100).await?;
+    let response_message = stream
+        .try_send_and_receive_message(&HyprvisorMessage::query(&client_info)?, 3)
+        .await?;
+
+    println!(
+        "{}",
+        output.render(
+            client_info.subscription_id,
+            &parse_response(response_message, client_info.subscription_id)?
+        )?
+    );
+
+    Ok(())
+}
+
+/// Log to the client log file, and to `console` as well at DEBUG level.
+pub(super) fn init_logger(filter: LevelFilter, console: fern::Output) -> HyprvisorResult<()> {
     let logger = fern::Dispatch::new()
         .format(move |out, message, record| {
             out.finish(format_args!(
@@ -52,51 +172,87 @@ fn init_logger(filter: LevelFilter) -> HyprvisorResult<()> {
             ))
         })
         .level(filter)
-        .chain(fern::log_file("/tmp/hyprvisor-client.log")?);
+        .chain(fern::log_file(&config().log.client_file)?);
 
     let logger = if LevelFilter::Debug == filter {
-        logger.chain(std::io::stdout())
+        logger.chain(console)
     } else {
         logger
     };
 
-    logger
-        .apply()
-        .map_err(|e| HyprvisorError::LoggerError(fern::InitError::SetLoggerError(e)))
+    logger.apply().map_err(HyprvisorError::LoggerError)
 }
 
-fn parse_opts(opts: SubscribeOpts) -> (SubscriptionID, u32) {
+fn parse_opts<O: Args + AsRef<QueryOpts>>(
+    opts: &SubscribeOpts<O>,
+    min_interval: u32,
+) -> ClientInfo {
+    let subscription_id = match opts {
+        SubscribeOpts::Workspaces { .. } => SubscriptionID::Workspaces,
+        SubscribeOpts::Window { .. } => SubscriptionID::Window,
+        SubscribeOpts::Wifi { .. } => SubscriptionID::Wifi,
+        SubscribeOpts::Bluetooth { .. } => SubscriptionID::Bluetooth,
+    };
+
+    let query_opts = opts.opts().as_ref();
+    let mut options = SubscriptionOptions {
+        min_interval,
+        ..default_options(subscription_id)
+    };
+
+    if let Some(ellipsis) = &query_opts.ellipsis {
+        options.ellipsis = ellipsis.clone();
+    }
+    if let Some(ellipsis_position) = query_opts.ellipsis_position {
+        options.ellipsis_position = ellipsis_position;
+    }
+
     match opts {
-        SubscribeOpts::Workspaces { fix_workspace } => (
-            SubscriptionID::Workspaces,
-            fix_workspace.map_or(0, |fw| {
-                log::warn!("Max workspaces = 10");
-                fw.min(10)
-            }),
-        ),
-        SubscribeOpts::Window { title_length } => (
-            SubscriptionID::Window,
-            title_length.map_or(50, |tl| {
-                log::warn!("Max title length = 100");
-                tl.min(u8::MAX.into())
-            }),
-        ),
-        SubscribeOpts::Wifi { ssid_length } => (
-            SubscriptionID::Wifi,
-            ssid_length.map_or(25, |sl| sl.min(u8::MAX.into())),
-        ),
-
-        SubscribeOpts::Bluetooth => (SubscriptionID::Bluetooth, 0),
+        SubscribeOpts::Workspaces {
+            fix_workspace: Some(fix_workspace),
+            ..
+        } => {
+            log::warn!("Max workspaces = 10");
+            options.fix_workspace = Some((*fix_workspace).min(10));
+        }
+        SubscribeOpts::Window {
+            title_length: Some(title_length),
+            ..
+        } => {
+            log::warn!("Max title length = 100");
+            options.title_length = Some((*title_length).min(u8::MAX.into()));
+        }
+        SubscribeOpts::Wifi {
+            ssid_length: Some(ssid_length),
+            ..
+        } => options.ssid_length = Some((*ssid_length).min(u8::MAX.into())),
+        _ => {}
+    }
+
+    ClientInfo::with_options(subscription_id, options)
+}
+
+/// Options of a subscription from the `[format]` defaults of the config.
+pub(super) fn default_options(subscription_id: SubscriptionID) -> SubscriptionOptions {
+    let config = config();
+    let defaults = &config.format;
+
+    SubscriptionOptions {
+        fix_workspace: defaults
+            .workspaces
+            .filter(|_| subscription_id == SubscriptionID::Workspaces),
+        title_length: (subscription_id == SubscriptionID::Window).then_some(defaults.title_length),
+        ssid_length: (subscription_id == SubscriptionID::Wifi).then_some(defaults.ssid_length),
+        ellipsis: defaults.ellipsis.clone(),
+        ellipsis_position: defaults.ellipsis_position,
+        min_interval: 0,
     }
 }
 
-async fn subscribe(subcription_id: SubscriptionID) -> HyprvisorResult<UnixStream> {
+pub(super) async fn subscribe(client_info: &ClientInfo) -> HyprvisorResult<UnixStream> {
     let stream = connect_to_socket(&HYPRVISOR_SOCKET, 1, 100).await?;
 
-    let message = HyprvisorMessage::from(ClientInfo {
-        subscription_id: subcription_id,
-        process_id: process::id(),
-    });
+    let message = HyprvisorMessage::try_from(client_info)?;
 
     stream.try_write_message(&message, 3).await?;
 
@@ -106,24 +262,23 @@ async fn subscribe(subcription_id: SubscriptionID) -> HyprvisorResult<UnixStream
 fn parse_response(
     message: HyprvisorMessage,
     subcription_id: SubscriptionID,
-    extra_data: &u32,
-) -> HyprvisorResult<String> {
+) -> HyprvisorResult<Value> {
     match subcription_id {
         SubscriptionID::Workspaces => {
             let ws_info: Vec<HyprWorkspaceInfo> = message.try_into()?;
-            ws_info.to_formatted_json(extra_data)
+            Ok(serde_json::to_value(&ws_info)?)
         }
         SubscriptionID::Window => {
             let window_info: HyprWindowInfo = message.try_into()?;
-            window_info.to_formatted_json(extra_data)
+            Ok(serde_json::to_value(&window_info)?)
         }
         SubscriptionID::Wifi => {
             let wifi_info: WifiInfo = message.try_into()?;
-            wifi_info.to_formatted_json(extra_data)
+            Ok(serde_json::to_value(&wifi_info)?)
         }
         SubscriptionID::Bluetooth => {
             let bt_info: BluetoothInfo = message.try_into()?;
-            bt_info.to_formatted_json(extra_data)
+            Ok(serde_json::to_value(&bt_info)?)
         }
         SubscriptionID::Invalid => {
             unreachable!()
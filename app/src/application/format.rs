@@ -0,0 +1,159 @@
+use super::types::{ClientInfo, EllipsisPosition, SubscriptionID};
+use crate::{
+    bluetooth::types::BluetoothInfo,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    hyprland::types::{FormattedInfo, HyprWindowInfo, HyprWorkspaceInfo},
+    wifi::types::WifiInfo,
+};
+
+use hyprvisor_client::ipc::message::{HyprvisorMessage, MessageType};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Shape a topic message with the options of one subscription.
+pub fn format_message(
+    client_info: &ClientInfo,
+    message: HyprvisorMessage,
+) -> HyprvisorResult<HyprvisorMessage> {
+    if message.message_type != MessageType::Response {
+        return Ok(message);
+    }
+
+    match client_info.subscription_id {
+        SubscriptionID::Workspaces => format_as::<Vec<HyprWorkspaceInfo>>(client_info, message),
+        SubscriptionID::Window => format_as::<HyprWindowInfo>(client_info, message),
+        SubscriptionID::Wifi => format_as::<WifiInfo>(client_info, message),
+        SubscriptionID::Bluetooth => format_as::<BluetoothInfo>(client_info, message),
+        SubscriptionID::Invalid => Err(HyprvisorError::Client(ClientError::InvalidSubscription)),
+    }
+}
+
+fn format_as<T>(
+    client_info: &ClientInfo,
+    message: HyprvisorMessage,
+) -> HyprvisorResult<HyprvisorMessage>
+where
+    T: FormattedInfo + TryFrom<HyprvisorMessage, Error = ClientError>,
+    HyprvisorMessage: TryFrom<T, Error = ClientError>,
+{
+    Ok(HyprvisorMessage::try_from(
+        T::try_from(message)?.to_formatted(&client_info.options),
+    )?)
+}
+
+/// Fit `text` in `max_width` columns, cutting whole grapheme clusters and
+/// putting `ellipsis` where the text was cut. Short enough text is left as is,
+/// and only what fits of the ellipsis is kept when it is wider than `max_width`.
+pub fn truncate(
+    text: &str,
+    max_width: usize,
+    ellipsis: &str,
+    position: EllipsisPosition,
+) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+
+    if ellipsis.width() > max_width {
+        let ellipsis: Vec<&str> = ellipsis.graphemes(true).collect();
+        return take_width(ellipsis.iter(), max_width);
+    }
+
+    let budget = max_width - ellipsis.width();
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+
+    match position {
+        EllipsisPosition::End => format!("{}{ellipsis}", take_width(graphemes.iter(), budget)),
+        EllipsisPosition::Start => {
+            format!("{ellipsis}{}", take_width_back(&graphemes, budget))
+        }
+        EllipsisPosition::Middle => format!(
+            "{}{ellipsis}{}",
+            take_width(graphemes.iter(), budget - budget / 2),
+            take_width_back(&graphemes, budget / 2)
+        ),
+    }
+}
+
+fn take_width<'a>(graphemes: impl Iterator<Item = &'a &'a str>, budget: usize) -> String {
+    let mut width = 0;
+
+    graphemes
+        .take_while(|grapheme| {
+            width += grapheme.width();
+            width <= budget
+        })
+        .copied()
+        .collect()
+}
+
+fn take_width_back(graphemes: &[&str], budget: usize) -> String {
+    let mut tail: Vec<&str> = Vec::new();
+    let mut width = 0;
+
+    for grapheme in graphemes.iter().rev() {
+        width += grapheme.width();
+        if width > budget {
+            break;
+        }
+        tail.push(grapheme);
+    }
+
+    tail.into_iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use EllipsisPosition::{End, Middle, Start};
+
+    #[test]
+    fn short_text_is_kept() {
+        assert_eq!(truncate("kitty", 5, "...", End), "kitty");
+        assert_eq!(truncate("kitty", 10, "...", Start), "kitty");
+        assert_eq!(truncate("", 0, "...", Middle), "");
+    }
+
+    #[test]
+    fn positions() {
+        assert_eq!(truncate("abcdefgh", 5, "…", End), "abcd…");
+        assert_eq!(truncate("abcdefgh", 5, "…", Start), "…efgh");
+        assert_eq!(truncate("abcdefgh", 5, "…", Middle), "ab…gh");
+        assert_eq!(truncate("abcdefgh", 6, "…", Middle), "abc…gh");
+        assert_eq!(truncate("abcdefgh", 4, "", End), "abcd");
+    }
+
+    #[test]
+    fn wide_characters() {
+        // Every character is two columns wide.
+        assert_eq!(truncate("日本語テキスト", 7, "…", End), "日本語…");
+        assert_eq!(truncate("日本語テキスト", 6, "…", End), "日本…");
+        assert_eq!(truncate("日本語テキスト", 6, "…", Start), "…スト");
+        assert_eq!(truncate("🎉🎉🎉🎉", 5, "…", End), "🎉🎉…");
+        assert_eq!(truncate("🎉🎉🎉🎉", 5, "…", Middle), "🎉…🎉");
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_letter() {
+        let text = "e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}";
+
+        assert_eq!(truncate(text, 5, "…", End), text);
+        assert_eq!(truncate(text, 4, "…", End), "e\u{301}e\u{301}e\u{301}…");
+        assert_eq!(truncate(text, 4, "…", Start), "…e\u{301}e\u{301}e\u{301}");
+    }
+
+    #[test]
+    fn result_never_exceeds_max_width() {
+        assert_eq!(truncate("abcdefgh", 3, "...", End), "...");
+        assert_eq!(truncate("abcdefgh", 2, "...", End), "..");
+        assert_eq!(truncate("abcdefgh", 1, "...", Middle), ".");
+        assert_eq!(truncate("abcdefgh", 0, "...", Start), "");
+
+        for max_width in 0..12 {
+            for position in [Start, Middle, End] {
+                assert!(truncate("日本語 text 🎉", max_width, "…", position).width() <= max_width);
+            }
+        }
+    }
+}
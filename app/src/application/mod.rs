@@ -1,5 +1,13 @@
+pub mod bar;
+pub mod broadcast;
 pub mod client;
+pub mod format;
+pub mod modules;
+pub mod reload;
 pub mod server;
+pub mod shutdown;
 pub mod standalone;
+pub mod status;
+pub mod systemd;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,157 @@
+use super::{
+    status,
+    types::{ListenerState, ModuleID, SubscriptionID},
+};
+use crate::{
+    bluetooth::{self, start_bluetooth_listener},
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    hyprland::{start_hyprland_listener, window, workspaces},
+    wifi::{self, start_wifi_listener},
+};
+
+use hyprvisor_client::ipc::message::HyprvisorMessage;
+
+use once_cell::sync::{Lazy, OnceCell};
+use std::{collections::HashMap, sync::Mutex};
+use tokio::task::JoinHandle;
+
+static ENABLED_MODULES: OnceCell<Vec<ModuleID>> = OnceCell::new();
+
+/// Listener task and number of users of every started module.
+static RUNNING_MODULES: Lazy<Mutex<HashMap<ModuleID, RunningModule>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct RunningModule {
+    listener: JoinHandle<HyprvisorResult<()>>,
+    users: usize,
+}
+
+/// Keeps the module of a topic running while it is alive.
+pub struct ModuleUser {
+    module: ModuleID,
+    started: bool,
+}
+
+pub fn set_enabled(modules: Vec<ModuleID>) {
+    for module in ModuleID::ALL {
+        status::set_listener_state(
+            module,
+            match modules.contains(&module) {
+                true => ListenerState::Idle,
+                false => ListenerState::Disabled,
+            },
+        );
+    }
+
+    log::info!(
+        "Enabled modules: {}",
+        modules
+            .iter()
+            .map(ModuleID::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let _ = ENABLED_MODULES.set(modules);
+}
+
+pub fn is_enabled(module: ModuleID) -> bool {
+    ENABLED_MODULES
+        .get()
+        .is_some_and(|modules| modules.contains(&module))
+}
+
+/// Whether the module serving a topic is started and has not given up.
+pub fn is_serving(subscription_id: SubscriptionID) -> bool {
+    ModuleID::serving(subscription_id).is_some_and(|module| {
+        RUNNING_MODULES
+            .lock()
+            .unwrap()
+            .get(&module)
+            .is_some_and(|running| !running.listener.is_finished())
+    })
+}
+
+/// Start the module serving a topic unless it is already running.
+/// `None` when the module is disabled.
+pub fn acquire(subscription_id: SubscriptionID) -> Option<ModuleUser> {
+    let module = ModuleID::serving(subscription_id)?;
+
+    if !is_enabled(module) {
+        return None;
+    }
+
+    let mut running_modules = RUNNING_MODULES.lock().unwrap();
+
+    let running = running_modules
+        .entry(module)
+        .or_insert_with(|| RunningModule {
+            listener: start_listener(module),
+            users: 0,
+        });
+
+    // A listener that gave up gets another chance with its next user.
+    let started = running.users == 0 || running.listener.is_finished();
+    if running.listener.is_finished() {
+        running.listener = start_listener(module);
+    }
+
+    running.users += 1;
+
+    Some(ModuleUser { module, started })
+}
+
+/// Current state of a topic, as a new subscriber receives it first.
+pub async fn snapshot(subscription_id: SubscriptionID) -> HyprvisorResult<HyprvisorMessage> {
+    match subscription_id {
+        SubscriptionID::Window => window::snapshot().await,
+        SubscriptionID::Workspaces => workspaces::snapshot().await,
+        SubscriptionID::Wifi => wifi::snapshot().await,
+        SubscriptionID::Bluetooth => bluetooth::snapshot().await,
+        SubscriptionID::Invalid => Err(HyprvisorError::Client(ClientError::InvalidSubscription)),
+    }
+}
+
+/// Stop every running module.
+pub fn stop_all() {
+    for (module, running) in RUNNING_MODULES.lock().unwrap().drain() {
+        log::debug!("Stop {module} module");
+        running.listener.abort();
+    }
+}
+
+impl ModuleUser {
+    /// Whether this user started the module, which has no data yet.
+    pub fn started(&self) -> bool {
+        self.started
+    }
+}
+
+impl Drop for ModuleUser {
+    fn drop(&mut self) {
+        let mut running_modules = RUNNING_MODULES.lock().unwrap();
+
+        let Some(running) = running_modules.get_mut(&self.module) else {
+            return;
+        };
+
+        running.users = running.users.saturating_sub(1);
+
+        if running.users == 0 {
+            log::info!("No subscriber left, stop {} module", self.module);
+            running.listener.abort();
+            running_modules.remove(&self.module);
+            status::set_listener_state(self.module, ListenerState::Idle);
+        }
+    }
+}
+
+fn start_listener(module: ModuleID) -> JoinHandle<HyprvisorResult<()>> {
+    log::info!("Start {module} module");
+
+    match module {
+        ModuleID::Hyprland => tokio::spawn(start_hyprland_listener()),
+        ModuleID::Wifi => tokio::spawn(start_wifi_listener()),
+        ModuleID::Bluetooth => tokio::spawn(start_bluetooth_listener()),
+    }
+}
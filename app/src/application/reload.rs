@@ -0,0 +1,71 @@
+use super::{broadcast, modules, types::SubscriptionID};
+use crate::{
+    config::{self, config},
+    error::HyprvisorResult,
+    global::LOG_LEVEL_OVERRIDE,
+};
+
+use log::LevelFilter;
+use std::time::Duration;
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    time::sleep,
+};
+
+const CONFIG_POLLING_INTERVAL: u64 = 1000;
+
+/// Log level from `--verbose`, then the config.
+pub fn log_level() -> LevelFilter {
+    LOG_LEVEL_OVERRIDE
+        .get()
+        .copied()
+        .unwrap_or_else(|| config().log.level.into())
+}
+
+/// Reload the config whenever the file changes or SIGHUP arrives.
+pub async fn watch_config() -> HyprvisorResult<()> {
+    let mut sighup = signal(SignalKind::hangup())?;
+    let mut modified = config::modified();
+
+    loop {
+        tokio::select! {
+            _ = sleep(Duration::from_millis(CONFIG_POLLING_INTERVAL)) => {
+                let current = config::modified();
+                if current == modified {
+                    continue;
+                }
+                modified = current;
+                log::info!("Config file changed");
+            }
+            _ = sighup.recv() => log::info!("Reload requested by SIGHUP"),
+        }
+
+        let _ = reload_config().await;
+    }
+}
+
+/// Swap in the new config and resend every topic of a running module, so
+/// subscribers pick up new icons and title rewrites right away. Subscribers
+/// stay connected.
+pub async fn reload_config() -> HyprvisorResult<()> {
+    config::reload()?;
+
+    log::set_max_level(log_level());
+
+    for subscription_id in [
+        SubscriptionID::Window,
+        SubscriptionID::Workspaces,
+        SubscriptionID::Wifi,
+        SubscriptionID::Bluetooth,
+    ] {
+        if !modules::is_serving(subscription_id) {
+            continue;
+        }
+
+        if let Ok(snapshot) = modules::snapshot(subscription_id).await {
+            let _ = broadcast::publish(subscription_id, snapshot);
+        }
+    }
+
+    Ok(())
+}
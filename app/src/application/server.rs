@@ -1,54 +1,112 @@
 use super::{
-    types::{ClientInfo, SubscriptionID},
-    utils::ping_daemon,
+    broadcast, format,
+    modules::{self, ModuleUser},
+    reload, shutdown, status, systemd,
+    types::{ClientInfo, Connection, ModuleID, PeerInfo, SubscriberInfo, SubscriptionID},
+    utils::{ping_daemon, prepare_socket_dir},
 };
 use crate::{
     application::utils::HYPRVISOR_SOCKET,
-    bluetooth::{self, start_bluetooth_listener},
-    error::{HyprvisorError, HyprvisorResult},
-    global::SUBSCRIBERS,
-    hyprland::{start_hyprland_listener, window, workspaces},
+    config,
+    dbus::start_dbus_service,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    global::{START_TIME, SUBSCRIBERS},
+    opts::DaemonOpts,
+};
+
+use humantime::format_rfc3339_seconds;
+use hyprvisor_client::{
     ipc::{
         message::{HyprvisorMessage, MessageType},
         HyprvisorReadSock, HyprvisorWriteSock,
     },
-    opts::CommandOpts,
-    wifi::{self, start_wifi_listener},
+    socket::current_uid,
+    types::Command,
 };
-
-use humantime::format_rfc3339_seconds;
 use log::LevelFilter;
+use once_cell::sync::Lazy;
 use std::{
     collections::HashMap,
-    fs,
+    fs::{self, Permissions},
+    os::unix::fs::PermissionsExt,
     time::{Duration, SystemTime},
 };
 use tokio::{
     net::{UnixListener, UnixStream},
-    time::sleep,
+    time::{sleep, timeout},
 };
 
-pub async fn start_server(filter: LevelFilter) -> HyprvisorResult<()> {
+const QUERY_WARMUP: u64 = 1000;
+const QUERY_LINGER: u64 = 30000;
+
+pub async fn start_server(opts: DaemonOpts, filter: LevelFilter) -> HyprvisorResult<()> {
     init_logger(filter)?;
 
-    if ping_daemon().await.is_ok() {
-        return Err(HyprvisorError::DaemonRunning);
-    }
+    let activated_listener = systemd::take_listener()?;
+    let socket_activated = activated_listener.is_some();
 
-    if fs::metadata(HYPRVISOR_SOCKET.as_str()).is_ok() {
-        fs::remove_file(HYPRVISOR_SOCKET.as_str())?;
-        log::debug!("Removed: {}", HYPRVISOR_SOCKET.as_str());
+    if !socket_activated {
+        prepare_socket_dir()?;
+
+        if ping_daemon().await.is_ok() {
+            return Err(HyprvisorError::DaemonRunning);
+        }
+
+        if fs::metadata(HYPRVISOR_SOCKET.as_str()).is_ok() {
+            fs::remove_file(HYPRVISOR_SOCKET.as_str())?;
+            log::debug!("Removed: {}", HYPRVISOR_SOCKET.as_str());
+        }
     }
 
     log::info!("-------------------- START HYPRVISOR DAEMON --------------------");
+    log::info!("Config: {}", config::config_path().display());
+
+    Lazy::force(&START_TIME);
+
+    let listener = match activated_listener {
+        Some(listener) => {
+            log::info!("Use socket passed by systemd");
+            UnixListener::from_std(listener)?
+        }
+        None => bind_ipc_socket()?,
+    };
 
-    tokio::spawn(start_hyprland_listener());
+    broadcast::start_rate_limiters(&opts.rate_limits);
 
-    tokio::spawn(start_wifi_listener());
+    let config = config::config();
+    let mut listeners = vec![tokio::spawn(reload::watch_config())];
 
-    tokio::spawn(start_bluetooth_listener());
+    modules::set_enabled(
+        opts.modules
+            .clone()
+            .unwrap_or_else(|| config.modules.enabled()),
+    );
 
-    listen_ipc_socket().await
+    if config.daemon.dbus && !opts.no_dbus {
+        listeners.push(tokio::spawn(start_dbus_service()));
+    }
+
+    let _ = systemd::notify(&format!(
+        "READY=1\nSTATUS=Listening on {}",
+        HYPRVISOR_SOCKET.as_str()
+    ));
+
+    if let Some(interval) = systemd::watchdog_interval() {
+        tokio::spawn(systemd::run_watchdog(interval));
+    }
+
+    let result = tokio::select! {
+        result = listen_ipc_socket(listener, opts) => result,
+        reason = shutdown::wait_for_shutdown() => reason.map(|reason| {
+            log::info!("Shutdown requested by {reason}");
+        }),
+    };
+
+    let _ = systemd::notify("STOPPING=1\nSTATUS=Shutting down");
+
+    shutdown::shutdown(listeners, !socket_activated).await;
+
+    result
 }
 
 fn init_logger(filter: LevelFilter) -> HyprvisorResult<()> {
@@ -62,8 +120,7 @@ fn init_logger(filter: LevelFilter) -> HyprvisorResult<()> {
                 message
             ))
         })
-        .level(filter)
-        .chain(fern::log_file("/tmp/hyprvisor-server.log")?);
+        .chain(fern::log_file(&config::config().log.daemon_file)?);
 
     let logger = if LevelFilter::Debug == filter {
         logger.chain(std::io::stdout())
@@ -71,107 +128,209 @@ fn init_logger(filter: LevelFilter) -> HyprvisorResult<()> {
         logger
     };
 
-    logger
-        .apply()
-        .map_err(|e| HyprvisorError::LoggerError(fern::InitError::SetLoggerError(e)))
+    logger.apply().map_err(HyprvisorError::LoggerError)?;
+
+    // The level is applied globally so that a config reload can change it.
+    log::set_max_level(filter);
+
+    Ok(())
 }
 
-async fn listen_ipc_socket() -> HyprvisorResult<()> {
+fn bind_ipc_socket() -> HyprvisorResult<UnixListener> {
     log::info!("Try to bind on socket: {}", HYPRVISOR_SOCKET.as_str());
     let listener = UnixListener::bind(HYPRVISOR_SOCKET.as_str())?;
+    fs::set_permissions(HYPRVISOR_SOCKET.as_str(), Permissions::from_mode(0o600))?;
     log::info!("Success");
 
+    Ok(listener)
+}
+
+async fn listen_ipc_socket(listener: UnixListener, opts: DaemonOpts) -> HyprvisorResult<()> {
+    let uid = current_uid();
+
     while let Ok((stream, _)) = listener.accept().await {
-        tokio::spawn(handle_connection(stream));
+        let peer = match PeerInfo::try_from(&stream) {
+            Ok(peer) => peer,
+            Err(e) => {
+                log::warn!("Failed to get peer credentials. Error: {e}");
+                continue;
+            }
+        };
+
+        let connection = Connection::new(peer);
+
+        if peer.uid != uid {
+            log::warn!("{connection} refused: not owned by uid {uid}.");
+            continue;
+        }
+
+        log::debug!("{connection} connected.");
+        tokio::spawn(handle_connection(stream, connection, opts.clone()));
     }
 
     Ok(())
 }
 
-async fn handle_connection(stream: UnixStream) -> HyprvisorResult<()> {
+async fn handle_connection(
+    stream: UnixStream,
+    connection: Connection,
+    opts: DaemonOpts,
+) -> HyprvisorResult<()> {
     let message = stream.try_read_message(3).await?;
     if !message.is_valid() {
-        return Err(HyprvisorError::InvalidMessage);
+        return Err(HyprvisorError::Client(ClientError::InvalidMessage));
     }
 
     match message.message_type {
         MessageType::Command => process_command(stream, message).await,
-        MessageType::Subscription => register_client(stream, message).await,
+        MessageType::Subscription => register_client(stream, connection, message, opts).await,
+        MessageType::Query => answer_query(stream, connection, message).await,
         MessageType::Response => {
             log::error!("Ignore Response message from client.");
-            Err(HyprvisorError::InvalidMessage)
+            Err(HyprvisorError::Client(ClientError::InvalidMessage))
+        }
+        MessageType::Shutdown => {
+            log::error!("Ignore Shutdown message from client, use the kill command.");
+            Err(HyprvisorError::Client(ClientError::InvalidMessage))
         }
     }
 }
 
 async fn process_command(stream: UnixStream, message: HyprvisorMessage) -> HyprvisorResult<()> {
     if message.header != size_of::<u8>() {
-        return Err(HyprvisorError::InvalidMessage);
+        return Err(HyprvisorError::Client(ClientError::InvalidMessage));
     }
 
-    match CommandOpts::try_from(message.payload[0])? {
-        CommandOpts::Ping => {
+    match Command::try_from(message.payload[0])? {
+        Command::Ping => {
             stream.write_message(HyprvisorMessage::from("Pong")).await?;
         }
-        CommandOpts::Kill => {
+        Command::Kill => {
             stream
                 .write_message(HyprvisorMessage::from("Server is shutting down..."))
                 .await?;
 
-            sleep(Duration::from_millis(100)).await;
-            std::process::exit(0);
+            shutdown::request_shutdown();
+        }
+        Command::Reload => {
+            let response = match reload::reload_config().await {
+                Ok(_) => "Config reloaded".to_string(),
+                Err(e) => format!("Config not reloaded. {e}"),
+            };
+
+            stream
+                .write_message(HyprvisorMessage::from(response.as_str()))
+                .await?;
+        }
+        Command::Status => {
+            let daemon_status = serde_json::to_string(&status::collect_status().await)?;
+            stream
+                .write_message(HyprvisorMessage::from(daemon_status.as_str()))
+                .await?;
         }
     }
 
     Ok(())
 }
 
-async fn register_client(stream: UnixStream, message: HyprvisorMessage) -> HyprvisorResult<()> {
-    let client_info = ClientInfo::try_from(message.payload.as_slice())?;
+async fn answer_query(
+    stream: UnixStream,
+    connection: Connection,
+    message: HyprvisorMessage,
+) -> HyprvisorResult<()> {
+    let client_info = read_client_info(&connection, &message)?;
+
+    log::info!("{connection} query {}", client_info.subscription_id);
+
+    let user = modules::acquire(client_info.subscription_id);
+
+    // A module started for this query needs a moment to publish its first state.
+    if user.as_ref().is_some_and(ModuleUser::started)
+        && ModuleID::serving(client_info.subscription_id) != Some(ModuleID::Hyprland)
+    {
+        let mut receiver = broadcast::subscribe(client_info.subscription_id);
+        let _ = timeout(Duration::from_millis(QUERY_WARMUP), receiver.recv()).await;
+    }
+
+    // Polling clients query again soon, keep the module warm for them.
+    if let Some(user) = user {
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(QUERY_LINGER)).await;
+            drop(user);
+        });
+    }
+
+    send_snapshot(&stream, &client_info).await
+}
+
+async fn register_client(
+    stream: UnixStream,
+    connection: Connection,
+    message: HyprvisorMessage,
+    opts: DaemonOpts,
+) -> HyprvisorResult<()> {
+    let client_info = read_client_info(&connection, &message)?;
 
     log::info!(
-        "Client pid {} subscribe to {}",
-        client_info.process_id,
-        client_info.subscription_id
+        "{connection} subscribe to {} (min interval {}ms)",
+        client_info.subscription_id,
+        client_info.options.min_interval
     );
 
-    match client_info.subscription_id {
-        SubscriptionID::Window => {
-            window::response_to_subscription(&stream).await?;
-        }
+    if client_info.subscription_id == SubscriptionID::Invalid {
+        return Err(HyprvisorError::Client(ClientError::InvalidSubscription));
+    }
 
-        SubscriptionID::Workspaces => {
-            workspaces::response_to_subscription(&stream).await?;
-        }
+    let user = modules::acquire(client_info.subscription_id);
+    if user.is_none() {
+        log::warn!(
+            "{connection} subscribed to {}, but its module is disabled.",
+            client_info.subscription_id
+        );
+    }
 
-        SubscriptionID::Wifi => {
-            wifi::response_to_subscription(&stream).await?;
-        }
+    let receiver = broadcast::subscribe(client_info.subscription_id);
 
-        SubscriptionID::Bluetooth => {
-            bluetooth::response_to_subscription(&stream).await?;
-        }
+    send_snapshot(&stream, &client_info).await?;
 
-        SubscriptionID::Invalid => {
-            return Err(HyprvisorError::InvalidSubscription);
-        }
-    };
+    let (reader, writer) = stream.into_split();
 
-    let (_, writer) = stream.into_split();
+    SUBSCRIBERS
+        .lock()
+        .await
+        .entry(client_info.subscription_id)
+        .or_insert(HashMap::new())
+        .insert(
+            connection.id,
+            SubscriberInfo {
+                peer: connection.peer,
+            },
+        );
 
-    //let subscribers = SUBSCRIBERS.clone();
+    log::info!("{connection} registered.");
 
-    let mut subscribers_ref = SUBSCRIBERS.lock().await;
-    subscribers_ref
-        .entry(client_info.subscription_id)
-        .or_insert(HashMap::new());
+    broadcast::serve_subscriber(connection, client_info, receiver, reader, writer, &opts).await;
+
+    Ok(())
+}
+
+fn read_client_info(
+    connection: &Connection,
+    message: &HyprvisorMessage,
+) -> HyprvisorResult<ClientInfo> {
+    ClientInfo::try_from(message.payload.as_slice())
+        .inspect_err(|e| {
+            log::warn!("{connection} sent an unreadable request, is it older than the daemon? {e}")
+        })
+        .map_err(HyprvisorError::Client)
+}
 
-    subscribers_ref
-        .get_mut(&client_info.subscription_id)
-        .unwrap()
-        .insert(client_info.process_id, writer);
+async fn send_snapshot(stream: &UnixStream, client_info: &ClientInfo) -> HyprvisorResult<()> {
+    let snapshot = modules::snapshot(client_info.subscription_id).await?;
 
-    log::info!("Client connected.");
+    stream
+        .write_message(format::format_message(client_info, snapshot)?)
+        .await?;
 
     Ok(())
 }
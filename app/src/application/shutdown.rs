@@ -0,0 +1,70 @@
+use super::{modules, utils::HYPRVISOR_SOCKET};
+use crate::{
+    error::HyprvisorResult,
+    global::{SHUTDOWN_SIGNAL, SUBSCRIBERS, TOPICS},
+};
+
+use hyprvisor_client::ipc::message::HyprvisorMessage;
+use std::{fs, time::Duration};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+
+const SHUTDOWN_TIMEOUT: u64 = 1000;
+const SHUTDOWN_POLLING_INTERVAL: u64 = 50;
+
+pub fn request_shutdown() {
+    SHUTDOWN_SIGNAL.notify_one();
+}
+
+/// Resolves with the reason once a kill command, SIGTERM or SIGINT arrives.
+pub async fn wait_for_shutdown() -> HyprvisorResult<&'static str> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sigint = signal(SignalKind::interrupt())?;
+
+    tokio::select! {
+        _ = SHUTDOWN_SIGNAL.notified() => Ok("kill command"),
+        _ = sigterm.recv() => Ok("SIGTERM"),
+        _ = sigint.recv() => Ok("SIGINT"),
+    }
+}
+
+/// Stop listeners, tell subscribers the daemon is stopping and clean up.
+/// The socket file is left in place when it belongs to a systemd socket unit.
+pub async fn shutdown(listeners: Vec<JoinHandle<HyprvisorResult<()>>>, remove_socket: bool) {
+    log::info!("Stop listeners");
+    for listener in listeners.iter() {
+        listener.abort();
+    }
+    modules::stop_all();
+
+    log::info!("Notify subscribers");
+    for sender in TOPICS.values() {
+        let _ = sender.send(HyprvisorMessage::shutdown("Daemon is stopping"));
+    }
+
+    let deadline = Instant::now() + Duration::from_millis(SHUTDOWN_TIMEOUT);
+    while Instant::now() < deadline && has_subscribers().await {
+        sleep(Duration::from_millis(SHUTDOWN_POLLING_INTERVAL)).await;
+    }
+
+    if remove_socket {
+        match fs::remove_file(HYPRVISOR_SOCKET.as_str()) {
+            Ok(_) => log::debug!("Removed: {}", HYPRVISOR_SOCKET.as_str()),
+            Err(e) => log::warn!("Failed to remove {}. Error: {e}", HYPRVISOR_SOCKET.as_str()),
+        }
+    }
+
+    log::info!("-------------------- STOP HYPRVISOR DAEMON --------------------");
+    log::logger().flush();
+}
+
+async fn has_subscribers() -> bool {
+    SUBSCRIBERS
+        .lock()
+        .await
+        .values()
+        .any(|subscribers| !subscribers.is_empty())
+}
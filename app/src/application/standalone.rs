@@ -1,11 +1,17 @@
-use super::utils::HYPRVISOR_SOCKET;
+use super::{
+    types::{DaemonStatus, StatusReport},
+    utils::HYPRVISOR_SOCKET,
+};
 use crate::{
-    error::{HyprvisorError, HyprvisorResult},
-    ipc::{connect_to_socket, HyprvisorRequestResponse},
+    error::{ClientError, HyprvisorError, HyprvisorResult},
     opts::CommandOpts,
 };
 
 use humantime::format_rfc3339_seconds;
+use hyprvisor_client::{
+    ipc::{connect_to_socket, HyprvisorRequestResponse},
+    types::Command,
+};
 use log::LevelFilter;
 use std::time::SystemTime;
 
@@ -16,16 +22,25 @@ pub async fn send_command(command: CommandOpts, filter: LevelFilter) -> Hyprviso
 
     log::info!("Send command to server: {}", command);
 
-    let response_message = stream.send_and_receive_message(command.into()).await?;
+    let response_message = stream
+        .send_and_receive_message(Command::from(command).into())
+        .await?;
 
     if !response_message.is_valid() {
-        return Err(HyprvisorError::InvalidResponse);
+        return Err(HyprvisorError::Client(ClientError::InvalidResponse));
     }
 
-    log::info!(
-        "Response from server: {}",
-        String::from_utf8(response_message.payload).map_err(|_| HyprvisorError::ParseError)?
-    );
+    let response = String::from_utf8(response_message.payload)
+        .map_err(|_| HyprvisorError::Client(ClientError::ParseError))?;
+
+    match command {
+        CommandOpts::Status { json: true } => println!("{response}"),
+        CommandOpts::Status { json: false } => {
+            let status: DaemonStatus = serde_json::from_str(&response)?;
+            println!("{}", StatusReport(&status))
+        }
+        _ => log::info!("Response from server: {response}"),
+    }
 
     Ok(())
 }
@@ -44,7 +59,5 @@ fn init_logger(filter: LevelFilter) -> HyprvisorResult<()> {
         .level(filter)
         .chain(std::io::stdout());
 
-    logger
-        .apply()
-        .map_err(|e| HyprvisorError::LoggerError(fern::InitError::SetLoggerError(e)))
+    logger.apply().map_err(HyprvisorError::LoggerError)
 }
@@ -0,0 +1,71 @@
+use super::{
+    types::{
+        DaemonStatus, ListenerState, ListenerStatus, ModuleID, SubscriberStatus, SubscriptionID,
+        TopicStatus,
+    },
+    utils::HYPRVISOR_SOCKET,
+};
+use crate::global::{LAST_UPDATES, LISTENER_STATES, START_TIME, SUBSCRIBERS};
+
+use humantime::format_rfc3339_seconds;
+use std::{env, time::SystemTime};
+
+pub fn set_listener_state(module: ModuleID, state: ListenerState) {
+    LISTENER_STATES.lock().unwrap().insert(module, state);
+}
+
+pub fn record_update(subscription_id: SubscriptionID) {
+    LAST_UPDATES
+        .lock()
+        .unwrap()
+        .insert(subscription_id, SystemTime::now());
+}
+
+pub async fn collect_status() -> DaemonStatus {
+    let mut listeners: Vec<ListenerStatus> = LISTENER_STATES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(&module, &state)| ListenerStatus { module, state })
+        .collect();
+    listeners.sort_by_key(|listener| listener.module);
+
+    let last_updates = LAST_UPDATES.lock().unwrap().clone();
+    let subscribers_ref = SUBSCRIBERS.lock().await;
+
+    let topics = [
+        SubscriptionID::Workspaces,
+        SubscriptionID::Window,
+        SubscriptionID::Wifi,
+        SubscriptionID::Bluetooth,
+    ]
+    .into_iter()
+    .map(|topic| TopicStatus {
+        topic,
+        last_update: last_updates
+            .get(&topic)
+            .map(|&time| format_rfc3339_seconds(time).to_string()),
+        subscribers: subscribers_ref
+            .get(&topic)
+            .map(|subscribers| {
+                subscribers
+                    .iter()
+                    .map(|(&connection, subscriber)| SubscriberStatus {
+                        connection,
+                        peer: subscriber.peer,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+    .collect();
+
+    DaemonStatus {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime: START_TIME.elapsed().unwrap_or_default().as_secs(),
+        hyprland_instance: env::var("HYPRLAND_INSTANCE_SIGNATURE").unwrap_or_default(),
+        socket: HYPRVISOR_SOCKET.to_string(),
+        listeners,
+        topics,
+    }
+}
@@ -0,0 +1,102 @@
+use super::types::{ListenerState, ModuleID};
+use crate::{error::HyprvisorResult, global::LISTENER_STATES};
+
+use std::{
+    env, io,
+    os::{
+        fd::{FromRawFd, RawFd},
+        linux::net::SocketAddrExt,
+        unix::net::{SocketAddr, UnixDatagram, UnixListener},
+    },
+    process,
+    time::Duration,
+};
+use tokio::time::sleep;
+
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// Take the listening socket passed by systemd socket activation, if any.
+pub fn take_listener() -> HyprvisorResult<Option<UnixListener>> {
+    let listen_pid = env::var("LISTEN_PID")
+        .ok()
+        .and_then(|pid| pid.parse::<u32>().ok());
+    let listen_fds = env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|fds| fds.parse::<i32>().ok())
+        .unwrap_or(0);
+
+    // The variables stay set: the runtime threads may read the environment, and
+    // children inheriting them ignore them as LISTEN_PID is not theirs.
+    if listen_pid != Some(process::id()) || listen_fds < 1 {
+        return Ok(None);
+    }
+
+    if listen_fds > 1 {
+        log::warn!("Received {listen_fds} sockets from systemd, only the first one is used.");
+    }
+
+    // SAFETY: systemd hands over ownership of the descriptors starting at SD_LISTEN_FDS_START.
+    let listener = unsafe { UnixListener::from_raw_fd(SD_LISTEN_FDS_START) };
+    listener.set_nonblocking(true)?;
+
+    Ok(Some(listener))
+}
+
+/// Send a state update to the service manager. Does nothing outside of systemd.
+pub fn notify(state: &str) -> io::Result<()> {
+    let notify_socket = match env::var("NOTIFY_SOCKET") {
+        Ok(path) => path,
+        Err(_) => return Ok(()),
+    };
+
+    let address = match notify_socket.strip_prefix('@') {
+        Some(name) => SocketAddr::from_abstract_name(name)?,
+        None => SocketAddr::from_pathname(&notify_socket)?,
+    };
+
+    UnixDatagram::unbound()?
+        .send_to_addr(state.as_bytes(), &address)
+        .map(|_| ())
+}
+
+/// Half of the watchdog timeout configured by `WatchdogSec=`, if enabled for this process.
+pub fn watchdog_interval() -> Option<Duration> {
+    if let Ok(pid) = env::var("WATCHDOG_PID") {
+        if pid.parse::<u32>().ok() != Some(process::id()) {
+            return None;
+        }
+    }
+
+    env::var("WATCHDOG_USEC")
+        .ok()
+        .and_then(|usec| usec.parse::<u64>().ok())
+        .filter(|&usec| usec > 0)
+        .map(|usec| Duration::from_micros(usec / 2))
+}
+
+pub async fn run_watchdog(interval: Duration) {
+    log::info!("Watchdog enabled, ping every {}ms", interval.as_millis());
+
+    loop {
+        sleep(interval).await;
+
+        if !is_healthy() {
+            log::warn!("Health check failed, skip watchdog ping.");
+            continue;
+        }
+
+        if let Err(e) = notify("WATCHDOG=1") {
+            log::warn!("Failed to ping watchdog. Error: {e}");
+        }
+    }
+}
+
+/// The daemon is useless without Hyprland events, so it is healthy as long as
+/// the Hyprland listener has not given up.
+fn is_healthy() -> bool {
+    LISTENER_STATES
+        .lock()
+        .unwrap()
+        .get(&ModuleID::Hyprland)
+        .is_some_and(|state| *state != ListenerState::GaveUp)
+}
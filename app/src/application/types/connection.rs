@@ -0,0 +1,33 @@
+use super::{ConnectionID, PeerInfo};
+
+use std::{
+    fmt::{Display, Formatter, Result},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+pub struct Connection {
+    pub id: ConnectionID,
+    pub peer: PeerInfo,
+}
+
+impl Connection {
+    /// Connection of `peer` with a new ID.
+    pub fn new(peer: PeerInfo) -> Self {
+        Connection {
+            id: ConnectionID(NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed)),
+            peer,
+        }
+    }
+}
+
+impl Display for Connection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Client {} ({})", self.id, self.peer)
+    }
+}
+
+pub struct SubscriberInfo {
+    pub peer: PeerInfo,
+}
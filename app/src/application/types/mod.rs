@@ -1,10 +1,16 @@
-pub mod client_info;
-pub mod subscription_id;
+pub mod connection;
+pub mod send_queue;
+pub mod status_report;
 
 use std::collections::HashMap;
-use tokio::net::unix::OwnedWriteHalf;
 
-pub use client_info::ClientInfo;
-pub use subscription_id::SubscriptionID;
+pub use hyprvisor_client::types::{
+    ClientInfo, ConnectionID, DaemonStatus, EllipsisPosition, ListenerState, ListenerStatus,
+    ModuleID, PeerInfo, SubscriberStatus, SubscriptionID, SubscriptionOptions, TopicStatus,
+};
 
-pub type Subscriber = HashMap<SubscriptionID, HashMap<u32, OwnedWriteHalf>>;
+pub use connection::{Connection, SubscriberInfo};
+pub use send_queue::{PushOutcome, SendQueue};
+pub use status_report::StatusReport;
+
+pub type Subscriber = HashMap<SubscriptionID, HashMap<ConnectionID, SubscriberInfo>>;
@@ -0,0 +1,73 @@
+use crate::opts::OverflowPolicy;
+
+use hyprvisor_client::ipc::message::HyprvisorMessage;
+use std::{collections::VecDeque, sync::Mutex};
+use tokio::sync::Notify;
+
+pub enum PushOutcome {
+    Queued,
+    DroppedOldest,
+    Coalesced,
+    Overflowed,
+}
+
+/// Bounded queue of messages waiting to be written to one subscriber.
+pub struct SendQueue {
+    messages: Mutex<VecDeque<HyprvisorMessage>>,
+    notify: Notify,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+impl SendQueue {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        SendQueue {
+            messages: Mutex::new(VecDeque::with_capacity(capacity)),
+            notify: Notify::new(),
+            capacity: capacity.max(1),
+            policy,
+        }
+    }
+
+    pub fn push(&self, message: HyprvisorMessage) -> PushOutcome {
+        let mut messages = self.messages.lock().unwrap();
+
+        let outcome = if messages.len() < self.capacity {
+            PushOutcome::Queued
+        } else {
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    messages.pop_front();
+                    PushOutcome::DroppedOldest
+                }
+                OverflowPolicy::Coalesce => {
+                    messages.clear();
+                    PushOutcome::Coalesced
+                }
+                OverflowPolicy::Disconnect => return PushOutcome::Overflowed,
+            }
+        };
+
+        messages.push_back(message);
+        self.notify.notify_one();
+
+        outcome
+    }
+
+    /// Replace everything queued with a last message, whatever the capacity.
+    pub fn push_last(&self, message: HyprvisorMessage) {
+        let mut messages = self.messages.lock().unwrap();
+        messages.clear();
+        messages.push_back(message);
+        self.notify.notify_one();
+    }
+
+    pub async fn pop(&self) -> HyprvisorMessage {
+        loop {
+            if let Some(message) = self.messages.lock().unwrap().pop_front() {
+                return message;
+            }
+            self.notify.notified().await;
+        }
+    }
+}
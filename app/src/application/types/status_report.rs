@@ -0,0 +1,58 @@
+use super::DaemonStatus;
+use crate::locale::Message;
+
+use humantime::format_duration;
+use std::{
+    fmt::{Display, Formatter, Result},
+    time::Duration,
+};
+
+/// Human readable `status` output, in the language of the user.
+pub struct StatusReport<'a>(pub &'a DaemonStatus);
+
+impl Display for StatusReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let status = self.0;
+
+        writeln!(f, "hyprvisor {}", status.version)?;
+        writeln!(
+            f,
+            "{}: {}",
+            Message::Uptime,
+            format_duration(Duration::from_secs(status.uptime))
+        )?;
+        writeln!(
+            f,
+            "{}: {}",
+            Message::HyprlandInstance,
+            status.hyprland_instance
+        )?;
+        writeln!(f, "{}: {}", Message::Socket, status.socket)?;
+
+        writeln!(f, "{}:", Message::Listeners)?;
+        for listener in status.listeners.iter() {
+            writeln!(
+                f,
+                "  {}: {}",
+                listener.module,
+                Message::ListenerState(&listener.state)
+            )?;
+        }
+
+        write!(f, "{}:", Message::Topics)?;
+        for topic in status.topics.iter() {
+            write!(
+                f,
+                "\n  {}: {}, {}",
+                topic.topic,
+                Message::LastUpdate(topic.last_update.as_deref()),
+                Message::Subscribers(topic.subscribers.len())
+            )?;
+            for subscriber in topic.subscribers.iter() {
+                write!(f, "\n    {} ({})", subscriber.connection, subscriber.peer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,45 +1,215 @@
 use crate::{
-    error::{HyprvisorError, HyprvisorResult},
+    config::config,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+};
+
+use hyprvisor_client::{
     ipc::{connect_to_socket, message::MessageType, HyprvisorReadSock, HyprvisorWriteSock},
-    opts::CommandOpts,
+    socket::{current_uid, default_socket_path},
+    types::Command,
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+use std::{
+    env,
+    fs::{self, DirBuilder, OpenOptions},
+    io::ErrorKind,
+    os::unix::{
+        fs::{DirBuilderExt, MetadataExt},
+        process::CommandExt,
+    },
+    path::Path,
+    process::{self, Stdio},
+    time::{Duration, SystemTime},
 };
+use tokio::time::sleep;
+
+const SPAWN_TIMEOUT: u64 = 5000;
+const SPAWN_POLLING_INTERVAL: u64 = 100;
 
-use once_cell::sync::Lazy;
-use std::env;
+static SOCKET_OVERRIDE: OnceCell<String> = OnceCell::new();
 
+/// Socket path, from `--socket`, then `HYPRVISOR_SOCKET`, then the config, then a
+/// per-user path namespaced by the Hyprland instance so each session gets its own daemon.
+/// A relative path is taken from the current directory.
 pub(super) static HYPRVISOR_SOCKET: Lazy<String> = Lazy::new(|| {
-    match env::var("HYPRLAND_INSTANCE_SIGNATURE") {
-        Ok(var) => var,
-        Err(_) => panic!("Is hyprland running?"),
+    let path = match (
+        SOCKET_OVERRIDE.get(),
+        env::var("HYPRVISOR_SOCKET"),
+        &config().socket,
+    ) {
+        (Some(path), _, _) => path.clone(),
+        (None, Err(_), Some(path)) => path.clone(),
+        _ => match default_socket_path() {
+            Ok(path) => path,
+            Err(_) => panic!("Is hyprland running?"),
+        },
     };
 
-    env::var("XDG_RUNTIME_DIR")
-        .map(|value| format!("{value}/hyprvisor.sock"))
-        .unwrap_or_else(|_| "/tmp/hyprvisor.sock".to_string())
+    match Path::new(&path).is_relative() {
+        true => env::current_dir()
+            .map(|dir| dir.join(&path).to_string_lossy().to_string())
+            .unwrap_or(path),
+        false => path,
+    }
+});
+
+/// Directory holding the socket, only accessible by its owner.
+static HYPRVISOR_SOCKET_DIR: Lazy<String> = Lazy::new(|| {
+    Path::new(HYPRVISOR_SOCKET.as_str())
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string())
 });
 
+pub fn set_socket_path(path: &str) {
+    let _ = SOCKET_OVERRIDE.set(path.to_string());
+}
+
+/// Create the socket directory with mode 0700, or make sure an existing one
+/// belongs to the current user and is not accessible by anyone else.
+pub(super) fn prepare_socket_dir() -> HyprvisorResult<()> {
+    let dir = HYPRVISOR_SOCKET_DIR.as_str();
+
+    match DirBuilder::new().mode(0o700).create(dir) {
+        Ok(_) => log::debug!("Created: {dir}"),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let metadata = fs::symlink_metadata(dir)?;
+
+    if !metadata.is_dir() || metadata.uid() != current_uid() {
+        log::error!("{dir} is not a directory owned by uid {}", current_uid());
+        return Err(HyprvisorError::InsecureSocket);
+    }
+
+    if metadata.mode() & 0o077 != 0 {
+        log::error!("{dir} is accessible by other users");
+        return Err(HyprvisorError::InsecureSocket);
+    }
+
+    Ok(())
+}
+
 pub(super) async fn ping_daemon() -> HyprvisorResult<()> {
     if std::fs::metadata(HYPRVISOR_SOCKET.as_str()).is_err() {
         log::info!("Server is not running");
-        return Err(HyprvisorError::NoDaemon);
+        return Err(HyprvisorError::Client(ClientError::NoDaemon));
     }
 
     let stream = connect_to_socket(&HYPRVISOR_SOCKET, 3, 100)
         .await
-        .map_err(|_| HyprvisorError::NoDaemon)?;
+        .map_err(|_| HyprvisorError::Client(ClientError::NoDaemon))?;
 
-    stream.write_message(CommandOpts::Ping.into()).await?;
+    stream.write_message(Command::Ping.into()).await?;
 
     let response = stream.read_message().await?;
 
     if response.message_type != MessageType::Response {
-        return Err(HyprvisorError::InvalidResponse);
+        return Err(HyprvisorError::Client(ClientError::InvalidResponse));
     }
 
     log::info!(
         "Response from server: {}",
-        String::from_utf8(response.payload).map_err(|_| HyprvisorError::ParseError)?
+        String::from_utf8(response.payload)
+            .map_err(|_| HyprvisorError::Client(ClientError::ParseError))?
     );
 
     Ok(())
 }
+
+/// Launch a detached daemon and wait until it answers.
+/// A lock file next to the socket makes sure concurrent clients spawn only one daemon.
+pub(super) async fn spawn_daemon() -> HyprvisorResult<()> {
+    prepare_socket_dir()?;
+
+    let lock_path = format!("{}.lock", HYPRVISOR_SOCKET.as_str());
+
+    let _lock = match SpawnLock::acquire(&lock_path)? {
+        Some(lock) => lock,
+        None => {
+            log::info!("Another client is spawning the daemon. Waiting...");
+            return wait_for_daemon().await;
+        }
+    };
+
+    if ping_daemon().await.is_ok() {
+        return Ok(());
+    }
+
+    log::info!("Spawn daemon");
+
+    let mut child = process::Command::new(env::current_exe()?)
+        .arg("--socket")
+        .arg(HYPRVISOR_SOCKET.as_str())
+        .arg("daemon")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()?;
+
+    // Reap the daemon whenever it exits so it does not linger as a zombie.
+    std::thread::spawn(move || child.wait());
+
+    wait_for_daemon().await
+}
+
+async fn wait_for_daemon() -> HyprvisorResult<()> {
+    for _ in 0..(SPAWN_TIMEOUT / SPAWN_POLLING_INTERVAL) {
+        sleep(Duration::from_millis(SPAWN_POLLING_INTERVAL)).await;
+        if ping_daemon().await.is_ok() {
+            return Ok(());
+        }
+    }
+
+    log::error!("Daemon did not come up in {SPAWN_TIMEOUT}ms");
+    Err(HyprvisorError::Client(ClientError::NoDaemon))
+}
+
+struct SpawnLock {
+    path: String,
+}
+
+impl SpawnLock {
+    fn acquire(path: &str) -> HyprvisorResult<Option<Self>> {
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(_) => {
+                    return Ok(Some(SpawnLock {
+                        path: path.to_string(),
+                    }))
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if !Self::is_stale(path) {
+                        return Ok(None);
+                    }
+                    log::warn!("Remove stale lock: {path}");
+                    let _ = fs::remove_file(path);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn is_stale(path: &str) -> bool {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map(|modified| {
+                SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default()
+                    > Duration::from_millis(SPAWN_TIMEOUT)
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for SpawnLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
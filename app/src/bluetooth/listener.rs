@@ -1,51 +1,69 @@
 use super::{
-    types::{BluetoothDeviceInfo, BluetoothInfo},
-    BLUETOOTH_DEVICES, BLUETOOTH_POWERED, MAX_ATTEMPT_RETRY, POLLING_INTERVAL,
+    types::{self, BluetoothDeviceInfo, BluetoothInfo},
+    BLUETOOTH_DEVICES, BLUETOOTH_POWERED,
 };
 use crate::{
-    application::types::SubscriptionID,
-    bluetooth::REBOOT_IWD_DELAY,
+    application::{
+        broadcast, status,
+        types::{ListenerState, ModuleID, SubscriptionID},
+    },
+    config::config,
     error::{HyprvisorError, HyprvisorResult},
-    global::SUBSCRIBERS,
-    ipc::{message::HyprvisorMessage, HyprvisorWriteSock},
 };
 
 use bluer::{Adapter, Address, Session};
+use hyprvisor_client::ipc::message::HyprvisorMessage;
 use std::{sync::atomic::Ordering, time::Duration};
-use tokio::{net::UnixStream, time::sleep};
+use tokio::time::sleep;
 
 pub async fn start_bluetooth_listener() -> HyprvisorResult<()> {
-    for attempt in 0..MAX_ATTEMPT_RETRY {
+    // Forget what a previous run saw, so that its first poll is published.
+    BLUETOOTH_POWERED.store(false, Ordering::SeqCst);
+    BLUETOOTH_DEVICES.lock().await.clear();
+
+    let max_attempt = config().bluetooth.max_retries;
+
+    for attempt in 0..max_attempt {
         log::info!(
             "Attemp to start bluetooth listener: {}/{}",
             attempt + 1,
-            MAX_ATTEMPT_RETRY
+            max_attempt
+        );
+        status::set_listener_state(
+            ModuleID::Bluetooth,
+            match attempt {
+                0 => ListenerState::Starting,
+                _ => ListenerState::Restarting {
+                    attempt: attempt + 1,
+                    max_attempt,
+                },
+            },
         );
 
         let _ = connect_to_bluetooth_session().await;
 
         log::warn!("Bluetooth is down. Rebooting...");
-        sleep(Duration::from_millis(REBOOT_IWD_DELAY)).await;
+        sleep(Duration::from_millis(config().bluetooth.retry_delay)).await;
     }
 
+    log::error!("Cannot start bluetooth listener. Out of attempt.");
+    status::set_listener_state(ModuleID::Bluetooth, ListenerState::GaveUp);
     Err(HyprvisorError::BluetoothError)
 }
 
-pub async fn response_to_subscription(stream: &UnixStream) -> HyprvisorResult<()> {
+pub async fn snapshot() -> HyprvisorResult<HyprvisorMessage> {
     let bt_info = match BLUETOOTH_POWERED.load(Ordering::SeqCst) {
         true => BluetoothInfo {
             powered: true,
             connected_devices: (BLUETOOTH_DEVICES.lock().await).clone(),
+            ..BluetoothInfo::default()
         },
         false => BluetoothInfo::default(),
     };
 
     log::debug!("Bluetooth info: {}", serde_json::to_string(&bt_info)?);
 
-    stream
-        .write_message(HyprvisorMessage::try_from(bt_info)?)
-        .await
-        .map(|_| ())
+    Ok(HyprvisorMessage::try_from(bt_info)?)
 }
 
 async fn connect_to_bluetooth_session() -> HyprvisorResult<()> {
@@ -73,12 +91,14 @@ async fn connect_to_bluetooth_session() -> HyprvisorResult<()> {
         }
     };
 
+    status::set_listener_state(ModuleID::Bluetooth, ListenerState::Running);
+
     polling_data(adapter, discovered_addresses).await
 }
 
 async fn polling_data(adapter: Adapter, discovered_addresses: Vec<Address>) -> HyprvisorResult<()> {
     loop {
-        sleep(Duration::from_millis(POLLING_INTERVAL)).await;
+        sleep(Duration::from_millis(config().bluetooth.polling_interval)).await;
 
         let powered = adapter
             .is_powered()
@@ -95,12 +115,10 @@ async fn polling_data(adapter: Adapter, discovered_addresses: Vec<Address>) -> H
             if let Ok(device) = adapter.device(*addr) {
                 if device.is_connected().await.unwrap_or(false) {
                     connected_devices.push(BluetoothDeviceInfo {
-                        name: device
-                            .name()
-                            .await
-                            .unwrap_or(None)
-                            .unwrap_or("Unknown device".to_string()),
-                        address: *addr,
+                        name: device.name().await.unwrap_or(None).unwrap_or_default(),
+                        address: types::Address(addr.0),
+                        kind: device.icon().await.unwrap_or(None).unwrap_or_default(),
+                        icon: String::new(),
                     });
                 }
             }
@@ -122,8 +140,8 @@ async fn handle_power_state(powered: bool) -> bool {
         let _ = broadcast_info(BluetoothInfo {
             powered,
             connected_devices: (BLUETOOTH_DEVICES.lock().await).clone(),
-        })
-        .await;
+            ..BluetoothInfo::default()
+        });
     }
 
     powered
@@ -140,35 +158,13 @@ async fn handle_connected_devices(connected_devices: Vec<BluetoothDeviceInfo>) {
     let _ = broadcast_info(BluetoothInfo {
         powered: true,
         connected_devices: (*current_devices).clone(),
-    })
-    .await;
+        ..BluetoothInfo::default()
+    });
 }
 
-async fn broadcast_info(bluetooth_info: BluetoothInfo) -> HyprvisorResult<()> {
-    let mut subscribers_ref = SUBSCRIBERS.lock().await;
-
-    let subscribers = match subscribers_ref.get_mut(&SubscriptionID::Bluetooth) {
-        Some(subs) if !subs.is_empty() => subs,
-        Some(_) | None => {
-            return Err(HyprvisorError::NoSubscriber);
-        }
-    };
-
-    let message: HyprvisorMessage = HyprvisorMessage::try_from(bluetooth_info)?;
-
-    let mut disconnected_pid = Vec::new();
-
-    for (pid, stream) in subscribers.iter_mut() {
-        if stream.try_write_message(&message, 2).await.is_err() {
-            log::debug!("Client {pid} is disconnected.");
-            disconnected_pid.push(*pid);
-        }
-    }
-
-    for pid in disconnected_pid {
-        log::info!("Remove {pid}");
-        subscribers.remove(&pid);
-    }
-
-    Ok(())
+fn broadcast_info(bluetooth_info: BluetoothInfo) -> HyprvisorResult<()> {
+    broadcast::publish(
+        SubscriptionID::Bluetooth,
+        HyprvisorMessage::try_from(bluetooth_info)?,
+    )
 }
@@ -1,48 +1,21 @@
-use crate::{
-    error::{HyprvisorError, HyprvisorResult},
-    hyprland::types::FormattedInfo,
-    ipc::message::{HyprvisorMessage, MessageType},
-};
+use crate::{config::config, hyprland::types::FormattedInfo};
 
-use bluer::Address;
-use serde::{Deserialize, Serialize};
+use hyprvisor_client::types::SubscriptionOptions;
 
-#[derive(Clone, Deserialize, Serialize, PartialEq, Eq)]
-pub struct BluetoothDeviceInfo {
-    pub name: String,
-    pub address: Address,
-}
-
-#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
-pub struct BluetoothInfo {
-    pub powered: bool,
-    pub connected_devices: Vec<BluetoothDeviceInfo>,
-}
+pub use hyprvisor_client::types::{Address, BluetoothDeviceInfo, BluetoothInfo};
 
 impl FormattedInfo for BluetoothInfo {
-    fn to_formatted_json(self, _extra_data: &u32) -> HyprvisorResult<String> {
-        serde_json::to_string(&self).map_err(HyprvisorError::JsonError)
-    }
-}
+    fn to_formatted(mut self, _options: &SubscriptionOptions) -> Self {
+        let config = config();
+
+        self.icon = config
+            .icons
+            .bluetooth(self.powered, !self.connected_devices.is_empty());
 
-impl TryFrom<HyprvisorMessage> for BluetoothInfo {
-    type Error = HyprvisorError;
-    fn try_from(message: HyprvisorMessage) -> HyprvisorResult<BluetoothInfo> {
-        if !message.is_valid() {
-            return Err(HyprvisorError::InvalidMessage);
+        for device in self.connected_devices.iter_mut() {
+            device.icon = config.icons.bluetooth_device(&device.kind);
         }
-        bincode::deserialize(&message.payload).map_err(HyprvisorError::BincodeError)
-    }
-}
 
-impl TryFrom<BluetoothInfo> for HyprvisorMessage {
-    type Error = HyprvisorError;
-    fn try_from(wifi_info: BluetoothInfo) -> Result<Self, Self::Error> {
-        let payload: Vec<u8> = bincode::serialize(&wifi_info)?;
-        Ok(HyprvisorMessage {
-            message_type: MessageType::Response,
-            header: payload.len(),
-            payload,
-        })
+        self
     }
 }
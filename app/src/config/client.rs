@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ClientConfig {
+    /// Same as `--spawn-daemon` on every listener, `get` and `bar`.
+    pub spawn_daemon: bool,
+}
@@ -0,0 +1,145 @@
+use crate::{application::types::ModuleID, opts::OverflowPolicy};
+
+use log::LevelFilter;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DaemonConfig {
+    /// Default of `daemon --queue-size`.
+    pub queue_size: usize,
+    /// Default of `daemon --overflow-policy`.
+    pub overflow_policy: OverflowPolicy,
+    /// Messages a topic keeps for subscribers that fall behind.
+    pub topic_capacity: usize,
+    /// Expose the topics on the D-Bus session bus.
+    pub dbus: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModulesConfig {
+    pub hyprland: bool,
+    pub wifi: bool,
+    pub bluetooth: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HyprlandConfig {
+    /// Size of the buffer reading the Hyprland sockets.
+    pub buffer_size: usize,
+    /// Connections to a socket, and restarts of the event listener, before giving up.
+    pub connect_attempts: u8,
+    /// Delay in milliseconds between two connection attempts.
+    pub connect_delay: u64,
+}
+
+/// Polling and retry policy of the wifi and bluetooth listeners.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ListenerConfig {
+    /// Delay in milliseconds between two polls.
+    pub polling_interval: u64,
+    /// Delay in milliseconds before restarting a listener that went down.
+    pub retry_delay: u64,
+    /// Restarts before the listener gives up.
+    pub max_retries: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LogConfig {
+    /// Overridden by `--verbose`.
+    pub level: LogLevel,
+    pub daemon_file: String,
+    pub client_file: String,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            queue_size: 8,
+            overflow_policy: OverflowPolicy::Coalesce,
+            topic_capacity: 16,
+            dbus: true,
+        }
+    }
+}
+
+impl Default for ModulesConfig {
+    fn default() -> Self {
+        Self {
+            hyprland: true,
+            wifi: true,
+            bluetooth: true,
+        }
+    }
+}
+
+impl Default for HyprlandConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: 8192,
+            connect_attempts: 3,
+            connect_delay: 100,
+        }
+    }
+}
+
+impl Default for ListenerConfig {
+    fn default() -> Self {
+        Self {
+            polling_interval: 500,
+            retry_delay: 2500,
+            max_retries: 10,
+        }
+    }
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            daemon_file: "/tmp/hyprvisor-server.log".to_string(),
+            client_file: "/tmp/hyprvisor-client.log".to_string(),
+        }
+    }
+}
+
+impl ModulesConfig {
+    pub fn enabled(&self) -> Vec<ModuleID> {
+        [
+            (ModuleID::Hyprland, self.hyprland),
+            (ModuleID::Wifi, self.wifi),
+            (ModuleID::Bluetooth, self.bluetooth),
+        ]
+        .into_iter()
+        .filter_map(|(module, enabled)| enabled.then_some(module))
+        .collect()
+    }
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
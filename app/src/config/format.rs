@@ -0,0 +1,27 @@
+use crate::application::types::EllipsisPosition;
+
+use serde::Deserialize;
+
+/// Defaults of the listener options, used when they are not given on the command line.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FormatConfig {
+    pub ellipsis: String,
+    pub ellipsis_position: EllipsisPosition,
+    pub title_length: u32,
+    pub ssid_length: u32,
+    /// Number of fixed workspaces, none when omitted.
+    pub workspaces: Option<u32>,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            ellipsis: "...".to_string(),
+            ellipsis_position: EllipsisPosition::End,
+            title_length: 50,
+            ssid_length: 25,
+            workspaces: None,
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{hyprland::types::HyprWorkspaceInfo, wifi::types::WifiState};
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Built-in icon set, every icon can be overridden in the config.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconTheme {
+    /// Glyphs of a patched Nerd Font.
+    #[default]
+    NerdFont,
+    /// Plain ASCII, for terminals and bars without patched fonts.
+    Ascii,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IconsConfig {
+    pub theme: IconTheme,
+    pub wifi: WifiIcons,
+    pub bluetooth: BluetoothIcons,
+    pub workspaces: WorkspaceIcons,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WifiIcons {
+    pub unknown: Option<String>,
+    pub disabled: Option<String>,
+    pub disconnected: Option<String>,
+    pub connecting: Option<String>,
+    /// One icon per signal level, weakest first.
+    pub connected: Option<Vec<String>>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BluetoothIcons {
+    pub off: Option<String>,
+    pub on: Option<String>,
+    pub connected: Option<String>,
+    /// Fallback for devices without an icon of their type.
+    pub device: Option<String>,
+    /// Icon per device type, e.g. `audio-headset = "..."`.
+    pub devices: HashMap<String, String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkspaceIcons {
+    pub active: Option<String>,
+    pub urgent: Option<String>,
+    pub occupied: Option<String>,
+    pub empty: Option<String>,
+}
+
+struct BuiltinIcons {
+    wifi_unknown: &'static str,
+    wifi_disabled: &'static str,
+    wifi_disconnected: &'static str,
+    wifi_connecting: &'static str,
+    wifi_connected: &'static [&'static str],
+    bluetooth_off: &'static str,
+    bluetooth_on: &'static str,
+    bluetooth_connected: &'static str,
+    bluetooth_device: &'static str,
+    bluetooth_devices: &'static [(&'static str, &'static str)],
+    workspace_active: &'static str,
+    workspace_urgent: &'static str,
+    workspace_occupied: &'static str,
+    workspace_empty: &'static str,
+}
+
+const NERD_FONT: BuiltinIcons = BuiltinIcons {
+    wifi_unknown: "󱚵",
+    wifi_disabled: "󰖪",
+    wifi_disconnected: "󱛅",
+    wifi_connecting: "󱛇",
+    wifi_connected: &["󰤟", "󰤢", "󰤥", "󰤨"],
+    bluetooth_off: "󰂲",
+    bluetooth_on: "󰂯",
+    bluetooth_connected: "󰂱",
+    bluetooth_device: "󰂯",
+    bluetooth_devices: &[
+        ("audio-card", "󰓃"),
+        ("audio-headphones", "󰋋"),
+        ("audio-headset", "󰋎"),
+        ("computer", "󰟀"),
+        ("input-gaming", "󰊴"),
+        ("input-keyboard", "󰌌"),
+        ("input-mouse", "󰍽"),
+        ("input-tablet", "󰓶"),
+        ("phone", "󰏲"),
+    ],
+    workspace_active: "",
+    workspace_urgent: "",
+    workspace_occupied: "",
+    workspace_empty: "",
+};
+
+const ASCII: BuiltinIcons = BuiltinIcons {
+    wifi_unknown: "?",
+    wifi_disabled: "x",
+    wifi_disconnected: "-",
+    wifi_connecting: "~",
+    wifi_connected: &["[#   ]", "[##  ]", "[### ]", "[####]"],
+    bluetooth_off: "BT off",
+    bluetooth_on: "BT",
+    bluetooth_connected: "BT+",
+    bluetooth_device: "[bt]",
+    bluetooth_devices: &[
+        ("audio-card", "[spk]"),
+        ("audio-headphones", "[hp]"),
+        ("audio-headset", "[hs]"),
+        ("computer", "[pc]"),
+        ("input-gaming", "[pad]"),
+        ("input-keyboard", "[kb]"),
+        ("input-mouse", "[ms]"),
+        ("input-tablet", "[tab]"),
+        ("phone", "[ph]"),
+    ],
+    workspace_active: "*",
+    workspace_urgent: "!",
+    workspace_occupied: "o",
+    workspace_empty: ".",
+};
+
+impl IconsConfig {
+    fn builtin(&self) -> &'static BuiltinIcons {
+        match self.theme {
+            IconTheme::NerdFont => &NERD_FONT,
+            IconTheme::Ascii => &ASCII,
+        }
+    }
+
+    /// `signal` is in percent, split evenly across the connected icons.
+    pub fn wifi(&self, state: &WifiState, signal: u8) -> String {
+        let builtin = self.builtin();
+        let icons = &self.wifi;
+
+        match state {
+            WifiState::Unknown => or(&icons.unknown, builtin.wifi_unknown),
+            WifiState::Disabled => or(&icons.disabled, builtin.wifi_disabled),
+            WifiState::Disconnected => or(&icons.disconnected, builtin.wifi_disconnected),
+            WifiState::Connecting => or(&icons.connecting, builtin.wifi_connecting),
+            WifiState::Connected => match &icons.connected {
+                Some(levels) if !levels.is_empty() => level(levels, signal).clone(),
+                _ => level(builtin.wifi_connected, signal).to_string(),
+            },
+        }
+    }
+
+    pub fn bluetooth(&self, powered: bool, connected: bool) -> String {
+        let builtin = self.builtin();
+        let icons = &self.bluetooth;
+
+        match (powered, connected) {
+            (false, _) => or(&icons.off, builtin.bluetooth_off),
+            (true, false) => or(&icons.on, builtin.bluetooth_on),
+            (true, true) => or(&icons.connected, builtin.bluetooth_connected),
+        }
+    }
+
+    pub fn bluetooth_device(&self, kind: &str) -> String {
+        let builtin = self.builtin();
+
+        if let Some(icon) = self.bluetooth.devices.get(kind) {
+            return icon.clone();
+        }
+
+        builtin
+            .bluetooth_devices
+            .iter()
+            .find(|(name, _)| *name == kind)
+            .map(|(_, icon)| icon.to_string())
+            .unwrap_or_else(|| or(&self.bluetooth.device, builtin.bluetooth_device))
+    }
+
+    pub fn workspace(&self, workspace: &HyprWorkspaceInfo) -> String {
+        let builtin = self.builtin();
+        let icons = &self.workspaces;
+
+        match (workspace.active, workspace.urgent, workspace.occupied) {
+            (true, _, _) => or(&icons.active, builtin.workspace_active),
+            (false, true, _) => or(&icons.urgent, builtin.workspace_urgent),
+            (false, false, true) => or(&icons.occupied, builtin.workspace_occupied),
+            (false, false, false) => or(&icons.empty, builtin.workspace_empty),
+        }
+    }
+}
+
+fn or(icon: &Option<String>, builtin: &str) -> String {
+    icon.clone().unwrap_or_else(|| builtin.to_string())
+}
+
+fn level<T>(levels: &[T], signal: u8) -> &T {
+    let index = usize::from(signal.min(100)) * levels.len() / 101;
+    &levels[index]
+}
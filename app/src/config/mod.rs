@@ -0,0 +1,196 @@
+pub mod client;
+pub mod daemon;
+pub mod format;
+pub mod icons;
+pub mod rate_limit;
+pub mod window;
+
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    locale::Language,
+};
+use client::ClientConfig;
+use daemon::{DaemonConfig, HyprlandConfig, ListenerConfig, LogConfig, ModulesConfig};
+use format::FormatConfig;
+use icons::IconsConfig;
+use rate_limit::RateLimitConfig;
+use window::WindowConfig;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+static CONFIG: Lazy<RwLock<Arc<Config>>> = Lazy::new(|| RwLock::new(Arc::new(Config::default())));
+
+/// Content of `$XDG_CONFIG_HOME/hyprvisor/config.toml`.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Socket path, below `--socket` and `HYPRVISOR_SOCKET`.
+    pub socket: Option<String>,
+    /// Language of the human readable text, e.g. `de`, below `LC_ALL`, `LC_MESSAGES` and `LANG`.
+    pub locale: Option<String>,
+    pub daemon: DaemonConfig,
+    pub client: ClientConfig,
+    pub modules: ModulesConfig,
+    pub hyprland: HyprlandConfig,
+    pub wifi: ListenerConfig,
+    pub bluetooth: ListenerConfig,
+    pub format: FormatConfig,
+    #[serde(rename = "rate-limit")]
+    pub rate_limit: RateLimitConfig,
+    pub log: LogConfig,
+    pub window: WindowConfig,
+    pub icons: IconsConfig,
+}
+
+/// Current configuration.
+pub fn config() -> Arc<Config> {
+    CONFIG.read().unwrap().clone()
+}
+
+pub fn config_path() -> PathBuf {
+    let config_home = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env::var("HOME").unwrap_or_default()).join(".config"));
+
+    config_home.join("hyprvisor").join("config.toml")
+}
+
+/// Read the config file. A missing file means the defaults.
+pub fn load() -> HyprvisorResult<()> {
+    *CONFIG.write().unwrap() = Arc::new(read()?);
+    Ok(())
+}
+
+/// Replace the current configuration, or keep it when the file is invalid.
+pub fn reload() -> HyprvisorResult<()> {
+    let config = read().inspect_err(|e| log::error!("Keep the current config. {e}"))?;
+
+    log::info!("Reloaded config: {}", config_path().display());
+    *CONFIG.write().unwrap() = Arc::new(config);
+
+    Ok(())
+}
+
+/// Modification time of the config file, `None` when there is none.
+pub fn modified() -> Option<SystemTime> {
+    fs::metadata(config_path())
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn read() -> HyprvisorResult<Config> {
+    let path = config_path();
+    let invalid =
+        |reason: String| HyprvisorError::InvalidConfig(format!("{}: {reason}", path.display()));
+
+    let config: Config = match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content).map_err(|e| invalid(e.to_string()))?,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+        Err(e) => return Err(e.into()),
+    };
+
+    config.validate().map_err(invalid)?;
+
+    Ok(config)
+}
+
+impl Config {
+    /// Check the values a well-formed file can still get wrong, all at once.
+    fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        let mut at_least = |key: &str, value: u64, min: u64| {
+            if value < min {
+                errors.push(format!("{key} must be at least {min}, got {value}"));
+            }
+        };
+
+        at_least("daemon.queue-size", self.daemon.queue_size as u64, 1);
+        at_least(
+            "daemon.topic-capacity",
+            self.daemon.topic_capacity as u64,
+            1,
+        );
+        at_least(
+            "hyprland.buffer-size",
+            self.hyprland.buffer_size as u64,
+            1024,
+        );
+        at_least(
+            "hyprland.connect-attempts",
+            self.hyprland.connect_attempts.into(),
+            1,
+        );
+
+        for (module, listener) in [("wifi", &self.wifi), ("bluetooth", &self.bluetooth)] {
+            at_least(
+                &format!("{module}.polling-interval"),
+                listener.polling_interval,
+                10,
+            );
+            at_least(
+                &format!("{module}.max-retries"),
+                listener.max_retries as u64,
+                1,
+            );
+        }
+
+        if self.format.title_length > u8::MAX.into() || self.format.ssid_length > u8::MAX.into() {
+            errors.push(format!(
+                "format.title-length and format.ssid-length must be at most {}",
+                u8::MAX
+            ));
+        }
+
+        if self
+            .format
+            .workspaces
+            .is_some_and(|workspaces| workspaces > 10)
+        {
+            errors.push("format.workspaces must be at most 10".to_string());
+        }
+
+        if self
+            .icons
+            .wifi
+            .connected
+            .as_ref()
+            .is_some_and(Vec::is_empty)
+        {
+            errors.push("icons.wifi.connected needs at least one icon".to_string());
+        }
+
+        if let Some(locale) = &self.locale {
+            if Language::from_locale(locale).is_none() {
+                errors.push(format!(
+                    "locale {locale} is not supported, use one of {}",
+                    Language::SUPPORTED
+                ));
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors.join("; ")),
+        }
+    }
+}
+
+/// Regex compiled while the config is parsed, so a bad pattern is reported with its location.
+#[derive(Clone, Debug)]
+pub struct Pattern(pub Regex);
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern).map(Pattern).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    application::types::SubscriptionID,
+    opts::{RateLimitEdge, RateLimitWindow},
+};
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Window of each topic, below `daemon --rate-limit`. A value is `<ms>` for the
+/// trailing edge or `"<ms>:<edge>"`, like the flag.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    #[serde(deserialize_with = "window")]
+    pub workspaces: Option<RateLimitWindow>,
+    #[serde(deserialize_with = "window")]
+    pub window: Option<RateLimitWindow>,
+    #[serde(deserialize_with = "window")]
+    pub wifi: Option<RateLimitWindow>,
+    #[serde(deserialize_with = "window")]
+    pub bluetooth: Option<RateLimitWindow>,
+}
+
+impl RateLimitConfig {
+    pub fn get(&self, subscription_id: SubscriptionID) -> Option<RateLimitWindow> {
+        match subscription_id {
+            SubscriptionID::Workspaces => self.workspaces,
+            SubscriptionID::Window => self.window,
+            SubscriptionID::Wifi => self.wifi,
+            SubscriptionID::Bluetooth => self.bluetooth,
+            SubscriptionID::Invalid => None,
+        }
+    }
+}
+
+fn window<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<RateLimitWindow>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Window {
+        Interval(u64),
+        Spec(String),
+    }
+
+    match Window::deserialize(deserializer)? {
+        Window::Interval(interval) => Ok(Some(RateLimitWindow {
+            interval,
+            edge: RateLimitEdge::Trailing,
+        })),
+        Window::Spec(spec) => spec.parse().map(Some).map_err(de::Error::custom),
+    }
+}
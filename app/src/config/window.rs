@@ -0,0 +1,36 @@
+use super::Pattern;
+
+use serde::Deserialize;
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowConfig {
+    /// Applied in order to the title of the active window.
+    pub rewrite: Vec<RewriteRule>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RewriteRule {
+    /// Only rewrite windows whose class matches. Every window when omitted.
+    pub class: Option<Pattern>,
+    #[serde(rename = "match")]
+    pub pattern: Pattern,
+    /// Replacement, `$1` or `$name` refer to capture groups.
+    #[serde(default)]
+    pub replace: String,
+}
+
+impl WindowConfig {
+    pub fn rewrite_title(&self, class: &str, title: &str) -> String {
+        self.rewrite
+            .iter()
+            .filter(|rule| rule.class.as_ref().is_none_or(|c| c.0.is_match(class)))
+            .fold(title.to_string(), |title, rule| {
+                rule.pattern
+                    .0
+                    .replace_all(&title, rule.replace.as_str())
+                    .into_owned()
+            })
+    }
+}
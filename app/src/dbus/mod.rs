@@ -0,0 +1,7 @@
+pub mod service;
+pub mod types;
+
+pub use service::start_dbus_service;
+
+const DBUS_NAME: &str = "org.hyprvisor.Daemon";
+const DBUS_PATH: &str = "/org/hyprvisor/Daemon";
@@ -0,0 +1,342 @@
+use super::{
+    types::{Bluetooth, Wifi, Window, Workspace},
+    DBUS_NAME, DBUS_PATH,
+};
+use crate::{
+    application::{
+        broadcast,
+        modules::{self, ModuleUser},
+        types::{ModuleID, SubscriptionID},
+    },
+    bluetooth::types::BluetoothInfo,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    hyprland::types::{FormattedInfo, HyprWindowInfo, HyprWorkspaceInfo},
+    wifi::types::WifiInfo,
+};
+
+use futures::StreamExt;
+use hyprvisor_client::{
+    ipc::message::{HyprvisorMessage, MessageType},
+    types::SubscriptionOptions,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast::error::RecvError;
+use zbus::{
+    connection, fdo::DBusProxy, interface, message::Header, object_server::InterfaceRef, Connection,
+};
+
+/// `org.hyprvisor.Daemon` interface. Every property holds the fields a
+/// `hyprvisor` listener would receive with the default options.
+struct DaemonInterface {
+    window: Window,
+    workspaces: Vec<Workspace>,
+    wifi: Wifi,
+    bluetooth: Bluetooth,
+    readers: Arc<Readers>,
+}
+
+/// A bus client that read a property counts as a subscriber of its topic
+/// until it leaves the bus. Keyed by topic and unique bus name.
+#[derive(Default)]
+struct Readers(Mutex<HashMap<(SubscriptionID, String), ModuleUser>>);
+
+#[interface(name = "org.hyprvisor.Daemon")]
+impl DaemonInterface {
+    #[zbus(property)]
+    fn active_window(
+        &self,
+        #[zbus(header)] header: Option<Header<'_>>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> Window {
+        self.readers
+            .track(SubscriptionID::Window, header, connection);
+        self.window.clone()
+    }
+
+    #[zbus(property)]
+    fn workspaces(
+        &self,
+        #[zbus(header)] header: Option<Header<'_>>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> Vec<Workspace> {
+        self.readers
+            .track(SubscriptionID::Workspaces, header, connection);
+        self.workspaces.clone()
+    }
+
+    #[zbus(property)]
+    fn wifi(
+        &self,
+        #[zbus(header)] header: Option<Header<'_>>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> Wifi {
+        self.readers.track(SubscriptionID::Wifi, header, connection);
+        self.wifi.clone()
+    }
+
+    #[zbus(property)]
+    fn bluetooth(
+        &self,
+        #[zbus(header)] header: Option<Header<'_>>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> Bluetooth {
+        self.readers
+            .track(SubscriptionID::Bluetooth, header, connection);
+        self.bluetooth.clone()
+    }
+}
+
+impl Readers {
+    /// Start the module of a topic for a new reader. `header` is `None` when the
+    /// value is read for a `PropertiesChanged` signal.
+    fn track(
+        &self,
+        subscription_id: SubscriptionID,
+        header: Option<Header<'_>>,
+        connection: &Connection,
+    ) {
+        let Some(reader) = header.as_ref().and_then(Header::sender) else {
+            return;
+        };
+
+        let mut readers = self.0.lock().unwrap();
+        let key = (subscription_id, reader.to_string());
+
+        if readers.contains_key(&key) {
+            return;
+        }
+
+        let Some(user) = modules::acquire(subscription_id) else {
+            return;
+        };
+
+        log::debug!("D-Bus client {reader} follows {subscription_id}");
+        readers.insert(key, user);
+
+        // Wifi and bluetooth publish their first poll, Hyprland only its changes.
+        if ModuleID::serving(subscription_id) == Some(ModuleID::Hyprland) {
+            tokio::spawn(refresh_property(connection.clone(), subscription_id));
+        }
+    }
+
+    fn forget(&self, reader: &str) {
+        self.0.lock().unwrap().retain(|(_, name), _| name != reader);
+    }
+}
+
+/// Serve the topics on the session bus until the daemon stops. Modules start
+/// when a bus client reads their properties. A missing session bus is not fatal,
+/// the Unix socket keeps working.
+pub async fn start_dbus_service() -> HyprvisorResult<()> {
+    let mut window_receiver = broadcast::subscribe(SubscriptionID::Window);
+    let mut workspaces_receiver = broadcast::subscribe(SubscriptionID::Workspaces);
+    let mut wifi_receiver = broadcast::subscribe(SubscriptionID::Wifi);
+    let mut bluetooth_receiver = broadcast::subscribe(SubscriptionID::Bluetooth);
+
+    let readers = Arc::new(Readers::default());
+    let interface = DaemonInterface {
+        window: Window::default(),
+        workspaces: Vec::new(),
+        wifi: Wifi::default(),
+        bluetooth: Bluetooth::default(),
+        readers: readers.clone(),
+    };
+
+    let connection = connection::Builder::session()
+        .and_then(|builder| builder.name(DBUS_NAME))
+        .and_then(|builder| builder.serve_at(DBUS_PATH, interface))
+        .map_err(dbus_error)?
+        .build()
+        .await
+        .map_err(dbus_error)?;
+
+    let interface_ref = connection
+        .object_server()
+        .interface::<_, DaemonInterface>(DBUS_PATH)
+        .await
+        .map_err(dbus_error)?;
+
+    let mut departures = DBusProxy::new(&connection)
+        .await
+        .map_err(dbus_error)?
+        .receive_name_owner_changed()
+        .await
+        .map_err(dbus_error)?;
+
+    log::info!("D-Bus service {DBUS_NAME} is ready");
+
+    loop {
+        let (subscription_id, message) = tokio::select! {
+            Some(signal) = departures.next() => {
+                if let Ok(args) = signal.args() {
+                    if args.new_owner().is_none() {
+                        readers.forget(args.name());
+                    }
+                }
+                continue;
+            }
+            message = window_receiver.recv() => (SubscriptionID::Window, message),
+            message = workspaces_receiver.recv() => (SubscriptionID::Workspaces, message),
+            message = wifi_receiver.recv() => (SubscriptionID::Wifi, message),
+            message = bluetooth_receiver.recv() => (SubscriptionID::Bluetooth, message),
+        };
+
+        let message = match message {
+            Ok(message) if message.message_type == MessageType::Response => message,
+            Ok(_) => continue,
+            Err(RecvError::Lagged(count)) => {
+                log::debug!("D-Bus service skipped {count} messages on {subscription_id}.");
+                continue;
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        };
+
+        if let Err(e) = update_property(&interface_ref, subscription_id, message).await {
+            log::warn!("Failed to update D-Bus property of {subscription_id}. Error: {e}");
+        }
+    }
+}
+
+async fn update_property(
+    interface_ref: &InterfaceRef<DaemonInterface>,
+    subscription_id: SubscriptionID,
+    message: HyprvisorMessage,
+) -> HyprvisorResult<()> {
+    let mut interface = interface_ref.get_mut().await;
+    let emitter = interface_ref.signal_emitter();
+
+    match subscription_id {
+        SubscriptionID::Window => {
+            interface.window = decode::<HyprWindowInfo>(message)?.into();
+            interface.active_window_changed(emitter).await
+        }
+        SubscriptionID::Workspaces => {
+            interface.workspaces = workspace_properties(decode(message)?);
+            interface.workspaces_changed(emitter).await
+        }
+        SubscriptionID::Wifi => {
+            interface.wifi = decode::<WifiInfo>(message)?.into();
+            interface.wifi_changed(emitter).await
+        }
+        SubscriptionID::Bluetooth => {
+            interface.bluetooth = decode::<BluetoothInfo>(message)?.into();
+            interface.bluetooth_changed(emitter).await
+        }
+        SubscriptionID::Invalid => {
+            return Err(HyprvisorError::Client(ClientError::InvalidSubscription))
+        }
+    }
+    .map_err(dbus_error)
+}
+
+/// Topic data as a listener with the default options receives it.
+fn decode<T>(message: HyprvisorMessage) -> HyprvisorResult<T>
+where
+    T: FormattedInfo + TryFrom<HyprvisorMessage, Error = ClientError>,
+{
+    Ok(T::try_from(message)?.to_formatted(&SubscriptionOptions::default()))
+}
+
+/// Set a property to the current state of its topic.
+async fn refresh_property(connection: Connection, subscription_id: SubscriptionID) {
+    let interface_ref = match connection
+        .object_server()
+        .interface::<_, DaemonInterface>(DBUS_PATH)
+        .await
+    {
+        Ok(interface_ref) => interface_ref,
+        Err(e) => return log::warn!("D-Bus interface is gone. Error: {e}"),
+    };
+
+    let result = match modules::snapshot(subscription_id).await {
+        Ok(snapshot) => update_property(&interface_ref, subscription_id, snapshot).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to refresh D-Bus property of {subscription_id}. Error: {e}");
+    }
+}
+
+fn workspace_properties(workspaces: Vec<HyprWorkspaceInfo>) -> Vec<Workspace> {
+    workspaces.into_iter().map(Workspace::from).collect()
+}
+
+fn dbus_error(e: zbus::Error) -> HyprvisorError {
+    log::error!("D-Bus error: {e}");
+    HyprvisorError::DBusError
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zbus::{fdo::IntrospectableProxy, zvariant::OwnedValue, Proxy};
+
+    const TEST_NAME: &str = "org.hyprvisor.Test";
+
+    /// Needs a session bus, e.g. `dbus-run-session cargo test`. Skipped without one.
+    #[tokio::test]
+    async fn properties_are_typed_dictionaries() {
+        let interface = DaemonInterface {
+            window: HyprWindowInfo {
+                class: "kitty".to_string(),
+                title: "nvim".to_string(),
+            }
+            .into(),
+            workspaces: workspace_properties(vec![HyprWorkspaceInfo {
+                active: true,
+                ..HyprWorkspaceInfo::default_workspace(2)
+            }]),
+            wifi: Wifi::default(),
+            bluetooth: Bluetooth::default(),
+            readers: Arc::default(),
+        };
+
+        let Ok(_server) = async {
+            connection::Builder::session()?
+                .name(TEST_NAME)?
+                .serve_at(DBUS_PATH, interface)?
+                .build()
+                .await
+        }
+        .await
+        else {
+            eprintln!("No session bus, skipped.");
+            return;
+        };
+
+        let client = Connection::session().await.unwrap();
+        let proxy = Proxy::new(&client, TEST_NAME, DBUS_PATH, DBUS_NAME)
+            .await
+            .unwrap();
+
+        let window: HashMap<String, OwnedValue> = proxy.get_property("ActiveWindow").await.unwrap();
+        assert_eq!(String::try_from(window["title"].clone()).unwrap(), "nvim");
+
+        let workspaces: Vec<HashMap<String, OwnedValue>> =
+            proxy.get_property("Workspaces").await.unwrap();
+        assert_eq!(u32::try_from(&workspaces[0]["id"]).unwrap(), 2);
+        assert!(bool::try_from(&workspaces[0]["active"]).unwrap());
+
+        let bluetooth: HashMap<String, OwnedValue> = proxy.get_property("Bluetooth").await.unwrap();
+        assert!(bluetooth.contains_key("connected_devices"));
+
+        let xml = IntrospectableProxy::builder(&client)
+            .destination(TEST_NAME)
+            .unwrap()
+            .path(DBUS_PATH)
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .introspect()
+            .await
+            .unwrap();
+        assert!(xml.contains(r#"<property name="Workspaces" type="aa{sv}" access="read"/>"#));
+        assert!(xml.contains(r#"<property name="Wifi" type="a{sv}" access="read"/>"#));
+    }
+}
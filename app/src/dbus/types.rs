@@ -0,0 +1,119 @@
+use crate::{
+    bluetooth::types::{BluetoothDeviceInfo, BluetoothInfo},
+    hyprland::types::{HyprWindowInfo, HyprWorkspaceInfo},
+    wifi::types::{WifiInfo, WifiState},
+};
+
+use zbus::zvariant::{Type, Value};
+
+// Properties are `a{sv}` dictionaries keyed like the JSON of the listeners,
+// so that consumers can bind to a field by name.
+
+#[derive(Clone, Default, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct Window {
+    pub class: String,
+    pub title: String,
+}
+
+#[derive(Clone, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct Workspace {
+    pub id: u32,
+    pub occupied: bool,
+    pub active: bool,
+    pub urgent: bool,
+    pub icon: String,
+}
+
+#[derive(Clone, Default, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct Wifi {
+    /// `disabled`, `disconnected`, `connecting`, `connected` or `unknown`.
+    pub state: String,
+    pub ssid: String,
+    pub signal: u8,
+    pub icon: String,
+}
+
+#[derive(Clone, Default, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct Bluetooth {
+    pub powered: bool,
+    pub connected_devices: Vec<BluetoothDevice>,
+    pub icon: String,
+}
+
+#[derive(Clone, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct BluetoothDevice {
+    pub name: String,
+    pub address: String,
+    pub kind: String,
+    pub icon: String,
+}
+
+impl From<HyprWindowInfo> for Window {
+    fn from(window: HyprWindowInfo) -> Self {
+        Window {
+            class: window.class,
+            title: window.title,
+        }
+    }
+}
+
+impl From<HyprWorkspaceInfo> for Workspace {
+    fn from(workspace: HyprWorkspaceInfo) -> Self {
+        Workspace {
+            id: workspace.id,
+            occupied: workspace.occupied,
+            active: workspace.active,
+            urgent: workspace.urgent,
+            icon: workspace.icon,
+        }
+    }
+}
+
+impl From<WifiInfo> for Wifi {
+    fn from(wifi: WifiInfo) -> Self {
+        let state = match wifi.state {
+            WifiState::Disabled => "disabled",
+            WifiState::Disconnected => "disconnected",
+            WifiState::Connecting => "connecting",
+            WifiState::Connected => "connected",
+            WifiState::Unknown => "unknown",
+        };
+
+        Wifi {
+            state: state.to_string(),
+            ssid: wifi.ssid,
+            signal: wifi.signal,
+            icon: wifi.icon,
+        }
+    }
+}
+
+impl From<BluetoothInfo> for Bluetooth {
+    fn from(bluetooth: BluetoothInfo) -> Self {
+        Bluetooth {
+            powered: bluetooth.powered,
+            connected_devices: bluetooth
+                .connected_devices
+                .into_iter()
+                .map(BluetoothDevice::from)
+                .collect(),
+            icon: bluetooth.icon,
+        }
+    }
+}
+
+impl From<BluetoothDeviceInfo> for BluetoothDevice {
+    fn from(device: BluetoothDeviceInfo) -> Self {
+        BluetoothDevice {
+            name: device.name,
+            address: device.address.to_string(),
+            kind: device.kind,
+            icon: device.icon,
+        }
+    }
+}
@@ -1,46 +1,51 @@
+pub use hyprvisor_client::error::HyprvisorError as ClientError;
+
 use std::{fmt::Display, io, result::Result};
 
 pub type HyprvisorResult<T> = Result<T, HyprvisorError>;
 
 #[derive(Debug)]
 pub enum HyprvisorError {
+    /// Error of the IPC shared with the client library.
+    Client(ClientError),
     DaemonRunning,
-    NoDaemon,
-    JsonError(serde_json::Error),
-    BincodeError(bincode::Error),
-    IoError(io::Error),
-    IpcError,
-    ParseError,
     NoSubscriber,
     WifiError,
     BluetoothError,
-    FalseAlarm,
-    LoggerError(fern::InitError),
-    InvalidMessage,
-    InvalidResponse,
-    InvalidSubscription,
+    DBusError,
+    LoggerError(log::SetLoggerError),
+    InsecureSocket,
+    InvalidTemplate(String),
+    InvalidConfig(String),
+    UnsupportedOutput(String),
+}
+
+impl From<ClientError> for HyprvisorError {
+    fn from(err: ClientError) -> Self {
+        HyprvisorError::Client(err)
+    }
 }
 
 impl From<io::Error> for HyprvisorError {
     fn from(err: io::Error) -> Self {
-        HyprvisorError::IoError(err)
+        HyprvisorError::Client(err.into())
     }
 }
 
 impl From<serde_json::Error> for HyprvisorError {
     fn from(err: serde_json::Error) -> Self {
-        HyprvisorError::JsonError(err)
+        HyprvisorError::Client(err.into())
     }
 }
 
 impl From<bincode::Error> for HyprvisorError {
     fn from(err: bincode::Error) -> Self {
-        HyprvisorError::BincodeError(err)
+        HyprvisorError::Client(err.into())
     }
 }
 
-impl From<fern::InitError> for HyprvisorError {
-    fn from(err: fern::InitError) -> Self {
+impl From<log::SetLoggerError> for HyprvisorError {
+    fn from(err: log::SetLoggerError) -> Self {
         HyprvisorError::LoggerError(err)
     }
 }
@@ -48,21 +53,17 @@ impl From<fern::InitError> for HyprvisorError {
 impl Display for HyprvisorError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            HyprvisorError::Client(err) => write!(f, "{err}"),
             HyprvisorError::DaemonRunning => write!(f, "Daemon is already running"),
-            HyprvisorError::NoDaemon => write!(f, "No daemon found"),
-            HyprvisorError::JsonError(err) => write!(f, "Json error: {err}"),
-            HyprvisorError::BincodeError(err) => write!(f, "Bincode error: {err}"),
-            HyprvisorError::IpcError => write!(f, "Inter-processes communication error"),
-            HyprvisorError::IoError(err) => write!(f, "IO error: {err}"),
-            HyprvisorError::ParseError => write!(f, "Parse error"),
             HyprvisorError::NoSubscriber => write!(f, "No subscriber"),
-            HyprvisorError::FalseAlarm => write!(f, "False alarm"),
             HyprvisorError::WifiError => write!(f, "Wifi error"),
             HyprvisorError::BluetoothError => write!(f, "Bluetooth error"),
+            HyprvisorError::DBusError => write!(f, "D-Bus error"),
             HyprvisorError::LoggerError(err) => write!(f, "Logger error: {err}"),
-            HyprvisorError::InvalidMessage => write!(f, "Invalid message"),
-            HyprvisorError::InvalidResponse => write!(f, "Invalid response"),
-            HyprvisorError::InvalidSubscription => write!(f, "Invalid subscription"),
+            HyprvisorError::InsecureSocket => write!(f, "Socket directory is not private"),
+            HyprvisorError::InvalidTemplate(reason) => write!(f, "Invalid template: {reason}"),
+            HyprvisorError::InvalidConfig(reason) => write!(f, "Invalid config: {reason}"),
+            HyprvisorError::UnsupportedOutput(reason) => write!(f, "Unsupported output: {reason}"),
         }
     }
 }
@@ -1,10 +1,45 @@
-use crate::application::types::Subscriber;
+use crate::{
+    application::types::{ListenerState, ModuleID, Subscriber, SubscriptionID},
+    config::config,
+};
 
-use once_cell::sync::Lazy;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use hyprvisor_client::ipc::message::HyprvisorMessage;
+use log::LevelFilter;
+use once_cell::sync::{Lazy, OnceCell};
+use std::{collections::HashMap, sync::Arc, time::SystemTime};
+use tokio::sync::{broadcast, mpsc, Mutex, Notify};
 
 pub static SUBSCRIBERS: Lazy<Arc<Mutex<Subscriber>>> =
     Lazy::new(|| Arc::new(Mutex::new(Subscriber::new())));
 
-pub static BUFFER_SIZE: Lazy<usize> = Lazy::new(|| 8192);
+pub static TOPICS: Lazy<HashMap<SubscriptionID, broadcast::Sender<HyprvisorMessage>>> =
+    Lazy::new(|| {
+        [
+            SubscriptionID::Workspaces,
+            SubscriptionID::Window,
+            SubscriptionID::Wifi,
+            SubscriptionID::Bluetooth,
+        ]
+        .into_iter()
+        .map(|id| (id, broadcast::channel(*TOPIC_CHANNEL_SIZE).0))
+        .collect()
+    });
+
+pub static RATE_LIMITERS: OnceCell<
+    HashMap<SubscriptionID, mpsc::UnboundedSender<HyprvisorMessage>>,
+> = OnceCell::new();
+
+pub static SHUTDOWN_SIGNAL: Lazy<Notify> = Lazy::new(Notify::new);
+
+pub static START_TIME: Lazy<SystemTime> = Lazy::new(SystemTime::now);
+
+pub static LISTENER_STATES: Lazy<std::sync::Mutex<HashMap<ModuleID, ListenerState>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+pub static LAST_UPDATES: Lazy<std::sync::Mutex<HashMap<SubscriptionID, SystemTime>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+pub static TOPIC_CHANNEL_SIZE: Lazy<usize> = Lazy::new(|| config().daemon.topic_capacity);
+
+/// Log level from `--verbose`, which a config reload does not change.
+pub static LOG_LEVEL_OVERRIDE: OnceCell<LevelFilter> = OnceCell::new();
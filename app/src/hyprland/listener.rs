@@ -1,17 +1,65 @@
 use super::{types::*, utils::*, window, workspaces};
-use crate::{error::HyprvisorResult, global::BUFFER_SIZE, ipc::*};
+use crate::{
+    application::{
+        status,
+        types::{ListenerState, ModuleID},
+    },
+    config::config,
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+};
+
+use hyprvisor_client::ipc::*;
+use std::time::Duration;
+use tokio::time::sleep;
 
 pub async fn start_hyprland_listener() -> HyprvisorResult<()> {
-    let event_socket = hyprland_socket(&HyprSocketType::Event);
+    let max_attempt = config().hyprland.connect_attempts.into();
+
+    for attempt in 0..max_attempt {
+        log::info!(
+            "Attempt to start Hyprland event listener: {}/{}",
+            attempt + 1,
+            max_attempt
+        );
+        status::set_listener_state(
+            ModuleID::Hyprland,
+            match attempt {
+                0 => ListenerState::Starting,
+                _ => ListenerState::Restarting {
+                    attempt: attempt + 1,
+                    max_attempt,
+                },
+            },
+        );
+
+        if let Err(e) = listen_to_events().await {
+            log::warn!("Lost Hyprland event socket: {e}");
+        }
 
-    log::info!("Start Hyprland event listener");
+        sleep(Duration::from_millis(config().hyprland.connect_delay)).await;
+    }
 
-    let mut stream = connect_to_socket(&event_socket, 1, 100).await?;
+    log::error!("Cannot start Hyprland event listener. Out of attempt.");
+    status::set_listener_state(ModuleID::Hyprland, ListenerState::GaveUp);
+    Err(HyprvisorError::Client(ClientError::NoHyprland))
+}
 
-    let mut buffer = vec![0; *BUFFER_SIZE];
+/// Handle events until the event socket goes away.
+async fn listen_to_events() -> HyprvisorResult<()> {
+    let mut stream = connect_to_socket(&hyprland_socket(&HyprSocketType::Event), 1, 100).await?;
+
+    status::set_listener_state(ModuleID::Hyprland, ListenerState::Running);
+
+    let mut buffer = vec![0; config().hyprland.buffer_size];
 
     loop {
-        match fetch_hyprland_event(&mut stream, &mut buffer).await {
+        let events = fetch_hyprland_event(&mut stream, &mut buffer).await?;
+
+        for address in events.urgent_windows() {
+            let _ = workspaces::mark_urgent(address).await;
+        }
+
+        match events {
             events if events.contains(&HyprEvent::WindowChanged) => {
                 handle_window_change().await;
                 handle_workspace_change().await;
@@ -20,7 +68,7 @@ pub async fn start_hyprland_listener() -> HyprvisorResult<()> {
                 if events.contains_at_least(&[
                     &HyprEvent::WorkspaceCreated,
                     &HyprEvent::WorkspaceDestroyed,
-                ]) =>
+                ]) || !events.urgent_windows().is_empty() =>
             {
                 handle_workspace_change().await;
             }
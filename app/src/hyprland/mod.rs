@@ -7,7 +7,7 @@ pub mod workspaces;
 pub use listener::start_hyprland_listener;
 
 use once_cell::sync::Lazy;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::Mutex;
 use types::{HyprWindowInfo, HyprWorkspaceInfo};
 
@@ -16,3 +16,6 @@ static CURRENT_WINDOW: Lazy<Arc<Mutex<HyprWindowInfo>>> =
 
 static CURRENT_WORKSPACES: Lazy<Arc<Mutex<Vec<HyprWorkspaceInfo>>>> =
     Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+static URGENT_WORKSPACES: Lazy<std::sync::Mutex<HashSet<u32>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashSet::new()));
@@ -5,6 +5,8 @@ pub enum HyprEvent {
     WorkspaceDestroyed,
     WindowChanged,
     Window2Changed,
+    /// Address of the window asking for attention, without `0x`.
+    Urgent(String),
     IgnoredEvent,
     // More events will be handle in the future
 }
@@ -15,12 +17,13 @@ impl From<&[u8]> for HyprEventList {
     fn from(buffer: &[u8]) -> Self {
         let mut evt_list: Vec<HyprEvent> = String::from_utf8_lossy(buffer)
             .lines()
-            .map(|line| match line.split(">>").next().unwrap_or_default() {
-                "activewindow" => HyprEvent::WindowChanged,
-                "workspace" => HyprEvent::WorkspaceChanged,
-                "activewindowv2" => HyprEvent::Window2Changed,
-                "createworkspace" => HyprEvent::WorkspaceCreated,
-                "destroyworkspace" => HyprEvent::WorkspaceDestroyed,
+            .map(|line| match line.split_once(">>").unwrap_or((line, "")) {
+                ("activewindow", _) => HyprEvent::WindowChanged,
+                ("workspace", _) => HyprEvent::WorkspaceChanged,
+                ("activewindowv2", _) => HyprEvent::Window2Changed,
+                ("createworkspace", _) => HyprEvent::WorkspaceCreated,
+                ("destroyworkspace", _) => HyprEvent::WorkspaceDestroyed,
+                ("urgent", address) => HyprEvent::Urgent(address.to_string()),
                 _ => HyprEvent::IgnoredEvent,
             })
             .collect();
@@ -37,4 +40,14 @@ impl HyprEventList {
     pub fn contains_at_least(&self, events: &[&HyprEvent]) -> bool {
         events.iter().any(|&event| self.contains(event))
     }
+
+    pub fn urgent_windows(&self) -> Vec<&str> {
+        self.0
+            .iter()
+            .filter_map(|event| match event {
+                HyprEvent::Urgent(address) => Some(address.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
 }
@@ -3,7 +3,7 @@ pub(super) mod socket_type;
 pub(super) mod window;
 pub(super) mod workspace;
 
-use crate::error::HyprvisorResult;
+use hyprvisor_client::types::SubscriptionOptions;
 
 pub use event::{HyprEvent, HyprEventList};
 pub use socket_type::HyprSocketType;
@@ -11,5 +11,5 @@ pub use window::HyprWindowInfo;
 pub use workspace::HyprWorkspaceInfo;
 
 pub trait FormattedInfo {
-    fn to_formatted_json(self, extra_data: &u32) -> HyprvisorResult<String>;
+    fn to_formatted(self, options: &SubscriptionOptions) -> Self;
 }
@@ -1,45 +1,21 @@
 use super::FormattedInfo;
-use crate::{
-    error::{HyprvisorError, HyprvisorResult},
-    ipc::message::{HyprvisorMessage, MessageType},
-};
+use crate::application::format::truncate;
 
-use serde::{Deserialize, Serialize};
+use hyprvisor_client::types::SubscriptionOptions;
 
-#[derive(Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
-pub struct HyprWindowInfo {
-    pub class: String,
-    pub title: String,
-}
+pub use hyprvisor_client::types::HyprWindowInfo;
 
 impl FormattedInfo for HyprWindowInfo {
-    fn to_formatted_json(mut self, extra_data: &u32) -> HyprvisorResult<String> {
-        if let Some(title) = self.title.get(..*extra_data as usize) {
-            self.title = format!("{}...", String::from_utf8_lossy(title.as_bytes()));
-        }
-
-        serde_json::to_string(&self).map_err(HyprvisorError::JsonError)
-    }
-}
-
-impl TryFrom<HyprvisorMessage> for HyprWindowInfo {
-    type Error = HyprvisorError;
-    fn try_from(message: HyprvisorMessage) -> HyprvisorResult<HyprWindowInfo> {
-        if !message.is_valid() {
-            return Err(HyprvisorError::InvalidMessage);
+    fn to_formatted(mut self, options: &SubscriptionOptions) -> Self {
+        if let Some(length) = options.title_length {
+            self.title = truncate(
+                &self.title,
+                length as usize,
+                &options.ellipsis,
+                options.ellipsis_position,
+            );
         }
-        bincode::deserialize(&message.payload).map_err(HyprvisorError::BincodeError)
-    }
-}
 
-impl TryFrom<HyprWindowInfo> for HyprvisorMessage {
-    type Error = HyprvisorError;
-    fn try_from(window_info: HyprWindowInfo) -> HyprvisorResult<HyprvisorMessage> {
-        let payload: Vec<u8> = bincode::serialize(&window_info)?;
-        Ok(HyprvisorMessage {
-            message_type: MessageType::Response,
-            header: payload.len(),
-            payload,
-        })
+        self
     }
 }
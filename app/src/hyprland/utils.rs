@@ -1,12 +1,13 @@
 use super::types::{HyprEventList, HyprSocketType};
-use crate::{global::BUFFER_SIZE, ipc::*, HyprvisorResult};
+use crate::{config::config, HyprvisorResult};
 
-use std::env;
+use hyprvisor_client::ipc::*;
+use std::{
+    env,
+    io::{self, ErrorKind},
+};
 use tokio::{io::AsyncReadExt, net::UnixStream};
 
-pub const HYPRLAND_SOCKET_CONNECT_ATTEMPT: u8 = 3;
-pub const HYPRLAND_SOCKET_CONNECT_DELAY: u64 = 100;
-
 pub(super) fn hyprland_socket(socket_type: &HyprSocketType) -> String {
     let instance_signature = env::var("HYPRLAND_INSTANCE_SIGNATURE").expect("Is Hyprland running?");
 
@@ -23,28 +24,32 @@ pub(super) fn hyprland_socket(socket_type: &HyprSocketType) -> String {
 pub(super) async fn send_hyprland_command(command: &str) -> HyprvisorResult<Vec<u8>> {
     log::debug!("send_hyprland_command: {}", command);
 
-    let mut buffer = vec![0; *BUFFER_SIZE];
+    let config = config();
+    let mut buffer = vec![0; config.hyprland.buffer_size];
 
-    connect_to_socket(
+    let recv_len = connect_to_socket(
         &hyprland_socket(&HyprSocketType::Command),
-        HYPRLAND_SOCKET_CONNECT_ATTEMPT,
-        HYPRLAND_SOCKET_CONNECT_DELAY,
+        config.hyprland.connect_attempts,
+        config.hyprland.connect_delay,
     )
     .await?
     .try_send_and_receive_bytes(command.as_bytes(), &mut buffer, 10)
-    .await
-    .map(|recv_len| buffer[..recv_len].to_vec())
+    .await?;
+
+    Ok(buffer[..recv_len].to_vec())
 }
 
+/// Wait for the next events. Hyprland closing the socket is an error.
 pub(super) async fn fetch_hyprland_event(
     stream: &mut UnixStream,
     buffer: &mut [u8],
-) -> HyprEventList {
+) -> HyprvisorResult<HyprEventList> {
     log::debug!("fetch_hyprland_event");
 
-    stream
-        .read(buffer)
-        .await
-        .map(|len| buffer[..len].into())
-        .expect("Connection closed from Hyprland event socket.")
+    match stream.read(buffer).await? {
+        0 => {
+            Err(io::Error::new(ErrorKind::UnexpectedEof, "Hyprland closed the event socket").into())
+        }
+        len => Ok(buffer[..len].into()),
+    }
 }
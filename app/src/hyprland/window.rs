@@ -1,19 +1,15 @@
 use super::{types::HyprWindowInfo, CURRENT_WINDOW};
 use crate::{
-    application::types::SubscriptionID,
-    error::{HyprvisorError, HyprvisorResult},
-    global::SUBSCRIBERS,
+    application::{broadcast, types::SubscriptionID},
+    config::config,
+    error::HyprvisorResult,
     hyprland::utils::send_hyprland_command,
-    ipc::{message::HyprvisorMessage, HyprvisorWriteSock},
 };
 
-use tokio::net::UnixStream;
+use hyprvisor_client::ipc::message::HyprvisorMessage;
 
-pub async fn response_to_subscription(stream: &UnixStream) -> HyprvisorResult<()> {
-    match get_hypr_active_window().await {
-        Ok(window) => stream.write_message(window.try_into()?).await.map(|_| ()),
-        Err(e) => Err(e),
-    }
+pub async fn snapshot() -> HyprvisorResult<HyprvisorMessage> {
+    Ok(get_hypr_active_window().await?.try_into()?)
 }
 
 pub(super) async fn handle_new_event() -> HyprvisorResult<()> {
@@ -27,44 +23,25 @@ pub(super) async fn handle_new_event() -> HyprvisorResult<()> {
 
     *current_window = window;
 
-    broadcast_info(&current_window).await
+    broadcast_info(&current_window)
 }
 
 async fn get_hypr_active_window() -> HyprvisorResult<HyprWindowInfo> {
     let json_data: serde_json::Value =
         serde_json::from_slice(&send_hyprland_command("j/activewindow").await?)?;
 
+    let class = json_data["class"].as_str().unwrap_or_default();
+    let title = json_data["title"].as_str().unwrap_or_default();
+
     Ok(HyprWindowInfo {
-        class: json_data["class"].as_str().unwrap_or_default().to_string(),
-        title: json_data["title"].as_str().unwrap_or_default().to_string(),
+        class: class.to_string(),
+        title: config().window.rewrite_title(class, title),
     })
 }
 
-async fn broadcast_info(window_info: &HyprWindowInfo) -> HyprvisorResult<()> {
-    let mut subscribers_ref = SUBSCRIBERS.lock().await;
-
-    let subscribers = match subscribers_ref.get_mut(&SubscriptionID::Window) {
-        Some(subs) if !subs.is_empty() => subs,
-        Some(_) | None => {
-            return Err(HyprvisorError::NoSubscriber);
-        }
-    };
-
-    let message: HyprvisorMessage = HyprvisorMessage::try_from(window_info.clone())?;
-
-    let mut disconnected_pid = Vec::new();
-
-    for (pid, stream) in subscribers.iter_mut() {
-        if stream.try_write_message(&message, 2).await.is_err() {
-            log::debug!("Client {pid} is disconnected.");
-            disconnected_pid.push(*pid);
-        }
-    }
-
-    for pid in disconnected_pid {
-        log::info!("Remove {pid}");
-        subscribers.remove(&pid);
-    }
-
-    Ok(())
+fn broadcast_info(window_info: &HyprWindowInfo) -> HyprvisorResult<()> {
+    broadcast::publish(
+        SubscriptionID::Window,
+        HyprvisorMessage::try_from(window_info.clone())?,
+    )
 }
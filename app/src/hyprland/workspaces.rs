@@ -1,19 +1,16 @@
-use super::{types::HyprWorkspaceInfo, utils::send_hyprland_command, CURRENT_WORKSPACES};
+use super::{
+    types::HyprWorkspaceInfo, utils::send_hyprland_command, CURRENT_WORKSPACES, URGENT_WORKSPACES,
+};
 use crate::{
-    application::types::SubscriptionID,
-    error::{HyprvisorError, HyprvisorResult},
-    global::SUBSCRIBERS,
-    ipc::{message::HyprvisorMessage, HyprvisorWriteSock},
+    application::{broadcast, types::SubscriptionID},
+    error::{ClientError, HyprvisorError, HyprvisorResult},
 };
 
+use hyprvisor_client::ipc::message::HyprvisorMessage;
 use serde_json::{from_slice, Value};
-use tokio::net::UnixStream;
 
-pub async fn response_to_subscription(stream: &UnixStream) -> HyprvisorResult<()> {
-    match get_hypr_workspace_info().await {
-        Ok(ws_info) => stream.write_message(ws_info.try_into()?).await.map(|_| ()),
-        Err(e) => Err(e),
-    }
+pub async fn snapshot() -> HyprvisorResult<HyprvisorMessage> {
+    Ok(get_hypr_workspace_info().await?.try_into()?)
 }
 
 pub(super) async fn handle_new_event() -> HyprvisorResult<()> {
@@ -27,7 +24,7 @@ pub(super) async fn handle_new_event() -> HyprvisorResult<()> {
 
     *current_workspaces = new_workspaces;
 
-    broadcast_info(&current_workspaces).await
+    broadcast_info(&current_workspaces)
 }
 
 async fn get_hypr_workspace_info() -> HyprvisorResult<Vec<HyprWorkspaceInfo>> {
@@ -40,6 +37,9 @@ async fn get_hypr_workspace_info() -> HyprvisorResult<Vec<HyprWorkspaceInfo>> {
         .as_u64()
         .unwrap_or_default() as u32;
 
+    let mut urgent_workspaces = URGENT_WORKSPACES.lock().unwrap();
+    urgent_workspaces.remove(&active_ws_id);
+
     match from_slice(&all_workspace)? {
         Value::Array(json_array) => Ok(json_array
             .iter()
@@ -47,38 +47,39 @@ async fn get_hypr_workspace_info() -> HyprvisorResult<Vec<HyprWorkspaceInfo>> {
                 id: js_obj["id"].as_u64().unwrap_or_default() as u32,
                 occupied: js_obj["windows"].as_i64().unwrap_or_default() > 0,
                 active: js_obj["id"].as_u64().unwrap_or_default() as u32 == active_ws_id,
+                urgent: urgent_workspaces
+                    .contains(&(js_obj["id"].as_u64().unwrap_or_default() as u32)),
+                icon: String::new(),
             })
-            .filter(|&ws| ws.active || ws.occupied)
+            .filter(|ws| ws.active || ws.occupied)
             .collect()),
-        _ => Err(HyprvisorError::ParseError),
+        _ => Err(HyprvisorError::Client(ClientError::ParseError)),
     }
 }
 
-async fn broadcast_info(workspace_info: &[HyprWorkspaceInfo]) -> HyprvisorResult<()> {
-    let mut subscribers_ref = SUBSCRIBERS.lock().await;
-
-    let ws_subscribers = match subscribers_ref.get_mut(&SubscriptionID::Workspaces) {
-        Some(subs) if !subs.is_empty() => subs,
-        Some(_) | None => {
-            return Err(HyprvisorError::NoSubscriber);
-        }
-    };
-
-    let message: HyprvisorMessage = HyprvisorMessage::try_from(workspace_info)?;
-
-    let mut disconnected_pid = Vec::new();
-
-    for (pid, stream) in ws_subscribers.iter_mut() {
-        if stream.try_write_message(&message, 2).await.is_err() {
-            log::debug!("Client {pid} is disconnected.");
-            disconnected_pid.push(*pid);
-        }
-    }
-
-    for pid in disconnected_pid {
-        log::info!("Remove pid: {pid}");
-        ws_subscribers.remove(&pid);
+/// Remember the workspace of a window asking for attention until it is visited.
+pub(super) async fn mark_urgent(address: &str) -> HyprvisorResult<()> {
+    let clients = from_slice::<Value>(&send_hyprland_command("j/clients").await?)?;
+    let address = format!("0x{address}");
+
+    let workspace = clients
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|client| client["address"].as_str() == Some(address.as_str()))
+        .and_then(|client| client["workspace"]["id"].as_u64());
+
+    if let Some(id) = workspace {
+        log::debug!("Workspace {id} is urgent");
+        URGENT_WORKSPACES.lock().unwrap().insert(id as u32);
     }
 
     Ok(())
 }
+
+fn broadcast_info(workspace_info: &[HyprWorkspaceInfo]) -> HyprvisorResult<()> {
+    broadcast::publish(
+        SubscriptionID::Workspaces,
+        HyprvisorMessage::try_from(workspace_info)?,
+    )
+}
@@ -0,0 +1,45 @@
+use super::Message;
+use crate::{application::types::ListenerState, wifi::types::WifiState};
+
+use std::fmt::{Formatter, Result};
+
+pub(super) fn write(message: &Message, f: &mut Formatter<'_>) -> Result {
+    match message {
+        Message::UnknownDevice => write!(f, "Unbekanntes Gerät"),
+        Message::Devices(count) => write!(f, "{count} Geräte"),
+        Message::Off => write!(f, "aus"),
+        Message::On => write!(f, "an"),
+        Message::Connected => write!(f, "verbunden"),
+        Message::BluetoothOff => write!(f, "Bluetooth aus"),
+        Message::BluetoothOn => write!(f, "Bluetooth an"),
+        Message::WifiState(state) => match state {
+            WifiState::Disabled => write!(f, "deaktiviert"),
+            WifiState::Disconnected => write!(f, "getrennt"),
+            WifiState::Connecting => write!(f, "verbindet"),
+            WifiState::Connected => write!(f, "verbunden"),
+            WifiState::Unknown => write!(f, "unbekannt"),
+        },
+        Message::Active => write!(f, "Aktiv"),
+        Message::Occupied => write!(f, "Belegt"),
+        Message::Uptime => write!(f, "Laufzeit"),
+        Message::HyprlandInstance => write!(f, "Hyprland-Instanz"),
+        Message::Socket => write!(f, "Socket"),
+        Message::Listeners => write!(f, "Listener"),
+        Message::Topics => write!(f, "Themen"),
+        Message::ListenerState(state) => match state {
+            ListenerState::Idle => write!(f, "wartet"),
+            ListenerState::Disabled => write!(f, "deaktiviert"),
+            ListenerState::Starting => write!(f, "startet"),
+            ListenerState::Running => write!(f, "läuft"),
+            ListenerState::Restarting {
+                attempt,
+                max_attempt,
+            } => write!(f, "Neustart ({attempt}/{max_attempt})"),
+            ListenerState::GaveUp => write!(f, "aufgegeben"),
+        },
+        Message::LastUpdate(time) => {
+            write!(f, "letzte Aktualisierung {}", time.unwrap_or("nie"))
+        }
+        Message::Subscribers(count) => write!(f, "{count} Abonnent(en)"),
+    }
+}
@@ -0,0 +1,43 @@
+use super::Message;
+use crate::{application::types::ListenerState, wifi::types::WifiState};
+
+use std::fmt::{Formatter, Result};
+
+pub(super) fn write(message: &Message, f: &mut Formatter<'_>) -> Result {
+    match message {
+        Message::UnknownDevice => write!(f, "Unknown device"),
+        Message::Devices(count) => write!(f, "{count} devices"),
+        Message::Off => write!(f, "off"),
+        Message::On => write!(f, "on"),
+        Message::Connected => write!(f, "connected"),
+        Message::BluetoothOff => write!(f, "Bluetooth off"),
+        Message::BluetoothOn => write!(f, "Bluetooth on"),
+        Message::WifiState(state) => match state {
+            WifiState::Disabled => write!(f, "disabled"),
+            WifiState::Disconnected => write!(f, "disconnected"),
+            WifiState::Connecting => write!(f, "connecting"),
+            WifiState::Connected => write!(f, "connected"),
+            WifiState::Unknown => write!(f, "unknown"),
+        },
+        Message::Active => write!(f, "Active"),
+        Message::Occupied => write!(f, "Occupied"),
+        Message::Uptime => write!(f, "Uptime"),
+        Message::HyprlandInstance => write!(f, "Hyprland instance"),
+        Message::Socket => write!(f, "Socket"),
+        Message::Listeners => write!(f, "Listeners"),
+        Message::Topics => write!(f, "Topics"),
+        Message::ListenerState(state) => match state {
+            ListenerState::Idle => write!(f, "idle"),
+            ListenerState::Disabled => write!(f, "disabled"),
+            ListenerState::Starting => write!(f, "starting"),
+            ListenerState::Running => write!(f, "running"),
+            ListenerState::Restarting {
+                attempt,
+                max_attempt,
+            } => write!(f, "restarting ({attempt}/{max_attempt})"),
+            ListenerState::GaveUp => write!(f, "gave up"),
+        },
+        Message::LastUpdate(time) => write!(f, "last update {}", time.unwrap_or("never")),
+        Message::Subscribers(count) => write!(f, "{count} subscriber(s)"),
+    }
+}
@@ -0,0 +1,45 @@
+use super::Message;
+use crate::{application::types::ListenerState, wifi::types::WifiState};
+
+use std::fmt::{Formatter, Result};
+
+pub(super) fn write(message: &Message, f: &mut Formatter<'_>) -> Result {
+    match message {
+        Message::UnknownDevice => write!(f, "Appareil inconnu"),
+        Message::Devices(count) => write!(f, "{count} appareils"),
+        Message::Off => write!(f, "éteint"),
+        Message::On => write!(f, "allumé"),
+        Message::Connected => write!(f, "connecté"),
+        Message::BluetoothOff => write!(f, "Bluetooth éteint"),
+        Message::BluetoothOn => write!(f, "Bluetooth allumé"),
+        Message::WifiState(state) => match state {
+            WifiState::Disabled => write!(f, "désactivé"),
+            WifiState::Disconnected => write!(f, "déconnecté"),
+            WifiState::Connecting => write!(f, "connexion"),
+            WifiState::Connected => write!(f, "connecté"),
+            WifiState::Unknown => write!(f, "inconnu"),
+        },
+        Message::Active => write!(f, "Actif"),
+        Message::Occupied => write!(f, "Occupés"),
+        Message::Uptime => write!(f, "Durée d'activité"),
+        Message::HyprlandInstance => write!(f, "Instance Hyprland"),
+        Message::Socket => write!(f, "Socket"),
+        Message::Listeners => write!(f, "Écouteurs"),
+        Message::Topics => write!(f, "Sujets"),
+        Message::ListenerState(state) => match state {
+            ListenerState::Idle => write!(f, "en attente"),
+            ListenerState::Disabled => write!(f, "désactivé"),
+            ListenerState::Starting => write!(f, "démarrage"),
+            ListenerState::Running => write!(f, "en marche"),
+            ListenerState::Restarting {
+                attempt,
+                max_attempt,
+            } => write!(f, "redémarrage ({attempt}/{max_attempt})"),
+            ListenerState::GaveUp => write!(f, "abandonné"),
+        },
+        Message::LastUpdate(time) => {
+            write!(f, "dernière mise à jour {}", time.unwrap_or("jamais"))
+        }
+        Message::Subscribers(count) => write!(f, "{count} abonné(s)"),
+    }
+}
@@ -0,0 +1,92 @@
+mod de;
+mod en;
+mod fr;
+mod vi;
+
+use crate::{application::types::ListenerState, config::config, wifi::types::WifiState};
+
+use once_cell::sync::Lazy;
+use std::{
+    env,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Language of the environment: `LC_ALL`, then `LC_MESSAGES`, then `LANG`.
+static ENV_LANGUAGE: Lazy<Language> = Lazy::new(|| {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| Language::from_locale(&value))
+        .unwrap_or(Language::English)
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Vietnamese,
+}
+
+/// Human readable text. JSON output never goes through it, so its values stay stable.
+pub enum Message<'a> {
+    UnknownDevice,
+    Devices(usize),
+    Off,
+    On,
+    Connected,
+    BluetoothOff,
+    BluetoothOn,
+    WifiState(&'a WifiState),
+    Active,
+    Occupied,
+    Uptime,
+    HyprlandInstance,
+    Socket,
+    Listeners,
+    Topics,
+    ListenerState(&'a ListenerState),
+    LastUpdate(Option<&'a str>),
+    Subscribers(usize),
+}
+
+impl Language {
+    pub const SUPPORTED: &'static str = "en, de, fr, vi";
+
+    /// Language of a locale name such as `de_DE.UTF-8` or `fr`.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default();
+
+        match language {
+            "en" | "C" | "POSIX" => Some(Language::English),
+            "de" => Some(Language::German),
+            "fr" => Some(Language::French),
+            "vi" => Some(Language::Vietnamese),
+            _ => None,
+        }
+    }
+}
+
+/// Language from the config, or else from the environment.
+pub fn language() -> Language {
+    config()
+        .locale
+        .as_deref()
+        .and_then(Language::from_locale)
+        .unwrap_or(*ENV_LANGUAGE)
+}
+
+impl Display for Message<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match language() {
+            Language::English => en::write(self, f),
+            Language::German => de::write(self, f),
+            Language::French => fr::write(self, f),
+            Language::Vietnamese => vi::write(self, f),
+        }
+    }
+}
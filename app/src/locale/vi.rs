@@ -0,0 +1,45 @@
+use super::Message;
+use crate::{application::types::ListenerState, wifi::types::WifiState};
+
+use std::fmt::{Formatter, Result};
+
+pub(super) fn write(message: &Message, f: &mut Formatter<'_>) -> Result {
+    match message {
+        Message::UnknownDevice => write!(f, "Thiết bị không xác định"),
+        Message::Devices(count) => write!(f, "{count} thiết bị"),
+        Message::Off => write!(f, "tắt"),
+        Message::On => write!(f, "bật"),
+        Message::Connected => write!(f, "đã kết nối"),
+        Message::BluetoothOff => write!(f, "Bluetooth tắt"),
+        Message::BluetoothOn => write!(f, "Bluetooth bật"),
+        Message::WifiState(state) => match state {
+            WifiState::Disabled => write!(f, "đã tắt"),
+            WifiState::Disconnected => write!(f, "mất kết nối"),
+            WifiState::Connecting => write!(f, "đang kết nối"),
+            WifiState::Connected => write!(f, "đã kết nối"),
+            WifiState::Unknown => write!(f, "không xác định"),
+        },
+        Message::Active => write!(f, "Hiện tại"),
+        Message::Occupied => write!(f, "Có cửa sổ"),
+        Message::Uptime => write!(f, "Thời gian chạy"),
+        Message::HyprlandInstance => write!(f, "Phiên Hyprland"),
+        Message::Socket => write!(f, "Socket"),
+        Message::Listeners => write!(f, "Trình lắng nghe"),
+        Message::Topics => write!(f, "Chủ đề"),
+        Message::ListenerState(state) => match state {
+            ListenerState::Idle => write!(f, "đang chờ"),
+            ListenerState::Disabled => write!(f, "đã tắt"),
+            ListenerState::Starting => write!(f, "đang khởi động"),
+            ListenerState::Running => write!(f, "đang chạy"),
+            ListenerState::Restarting {
+                attempt,
+                max_attempt,
+            } => write!(f, "đang khởi động lại ({attempt}/{max_attempt})"),
+            ListenerState::GaveUp => write!(f, "đã bỏ cuộc"),
+        },
+        Message::LastUpdate(time) => {
+            write!(f, "cập nhật lần cuối {}", time.unwrap_or("chưa có"))
+        }
+        Message::Subscribers(count) => write!(f, "{count} người đăng ký"),
+    }
+}
@@ -1,10 +1,13 @@
 mod application;
 mod bluetooth;
+mod config;
+mod dbus;
 mod error;
 mod global;
 mod hyprland;
-mod ipc;
+mod locale;
 mod opts;
+mod output;
 mod wifi;
 
 use crate::{
@@ -12,28 +15,47 @@ use crate::{
     opts::{Action, Opts},
 };
 
+use std::process::ExitCode;
+
 #[tokio::main]
-async fn main() -> HyprvisorResult<()> {
+async fn main() -> ExitCode {
     let opts = Opts::from_env();
-    run(&opts).await?;
 
-    Ok(())
+    match run(opts).await {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-async fn run(opts: &Opts) -> HyprvisorResult<()> {
-    let level_filter = if opts.verbose {
-        log::LevelFilter::Debug
-    } else {
-        log::LevelFilter::Info
-    };
+async fn run(opts: Opts) -> HyprvisorResult<()> {
+    config::load()?;
+
+    if let Some(socket) = &opts.socket {
+        application::utils::set_socket_path(socket);
+    }
+
+    if opts.verbose {
+        let _ = global::LOG_LEVEL_OVERRIDE.set(log::LevelFilter::Debug);
+    }
+
+    let level_filter = application::reload::log_level();
 
     match opts.action {
-        Action::Daemon => {
-            application::server::start_server(level_filter).await?;
+        Action::Daemon(daemon_opts) => {
+            application::server::start_server(daemon_opts, level_filter).await?;
         }
         Action::Command(command) => {
             application::standalone::send_command(command, level_filter).await?;
         }
+        Action::Get { topic } => {
+            application::client::query(topic, level_filter).await?;
+        }
+        Action::Bar(bar_opts) => {
+            application::bar::start_bar(bar_opts, level_filter).await?;
+        }
         Action::Listen(subscription) => {
             application::client::start_client(subscription, level_filter).await?;
         }
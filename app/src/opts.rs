@@ -1,12 +1,14 @@
-use crate::error::HyprvisorError;
+use crate::application::types::ModuleID;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use hyprvisor_client::types::{Command, EllipsisPosition, SubscriptionID};
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 #[derive(Deserialize, Serialize, PartialEq)]
 pub struct Opts {
     pub verbose: bool,
+    pub socket: Option<String>,
     pub action: Action,
 }
 
@@ -18,6 +20,11 @@ struct RawOpts {
     #[arg(long = "verbose", short = 'v')]
     verbose: bool,
 
+    /// Path of the daemon socket. Overrides HYPRVISOR_SOCKET.
+    /// Its directory must belong to you with mode 0700, and is created so if missing.
+    #[arg(long = "socket", global = true)]
+    socket: Option<String>,
+
     #[command(subcommand)]
     action: Action,
 }
@@ -25,15 +32,134 @@ struct RawOpts {
 #[derive(Subcommand, Serialize, Deserialize, PartialEq)]
 pub enum Action {
     #[command(name = "daemon", alias = "d")]
-    Daemon,
+    Daemon(DaemonOpts),
 
     #[command(flatten)]
     Command(CommandOpts),
 
+    /// Print the current value of a topic and exit.
+    #[command(name = "get", alias = "g")]
+    Get {
+        #[command(subcommand)]
+        topic: SubscribeOpts<QueryOpts>,
+    },
+
+    /// Stream several topics as an i3bar/swaybar status command.
+    #[command(name = "bar")]
+    Bar(BarOpts),
+
     #[command(flatten)]
     Listen(SubscribeOpts),
 }
 
+#[derive(Clone, PartialEq, Deserialize, Serialize, Args)]
+pub struct DaemonOpts {
+    /// Maximum number of messages queued for a single subscriber [default: 8].
+    #[arg(long = "queue-size")]
+    pub queue_size: Option<usize>,
+
+    /// What to do when a subscriber's queue is full [default: coalesce].
+    #[arg(long = "overflow-policy", value_enum)]
+    pub overflow_policy: Option<OverflowPolicy>,
+
+    /// Coalesce bursts of updates on a topic, e.g. `window=50` or `workspaces=100:leading`.
+    /// The edge is `trailing` (default, send the latest update once per MS) or `leading`
+    /// (send the first update right away as well), `both` is the same as `leading`.
+    /// Overrides `[rate-limit]` in the config.
+    #[arg(long = "rate-limit", value_name = "TOPIC=MS[:EDGE]")]
+    pub rate_limits: Vec<RateLimit>,
+
+    /// Modules the daemon may start among `hyprland`, `wifi` and `bluetooth`, e.g. `hyprland,wifi`.
+    /// Overrides `[modules]` in the config.
+    #[arg(long = "modules", value_delimiter = ',', value_parser = ModuleID::from_str)]
+    pub modules: Option<Vec<ModuleID>>,
+
+    /// Do not expose the topics on the D-Bus session bus.
+    #[arg(long = "no-dbus")]
+    pub no_dbus: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverflowPolicy {
+    /// Drop the oldest queued message.
+    DropOldest,
+    /// Drop every queued message and keep only the latest one.
+    Coalesce,
+    /// Disconnect the subscriber.
+    Disconnect,
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize, Args)]
+pub struct BarOpts {
+    /// Topics shown as blocks, from left to right.
+    #[arg(required = true, value_parser = parse_topic)]
+    pub topics: Vec<SubscriptionID>,
+
+    /// Text of a block, e.g. `wifi={icon} {ssid}`.
+    #[arg(long = "format", value_name = "TOPIC=TEMPLATE")]
+    pub formats: Vec<BlockFormat>,
+
+    /// Shell command run when a block is clicked, e.g. `wifi:3=iwgtk`.
+    /// Without a button, any button runs it.
+    #[arg(long = "on-click", value_name = "TOPIC[:BUTTON]=COMMAND")]
+    pub on_click: Vec<ClickCommand>,
+
+    /// Start the daemon in the background if it is not running.
+    #[arg(long = "spawn-daemon")]
+    pub spawn_daemon: bool,
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct BlockFormat {
+    pub subscription_id: SubscriptionID,
+    pub template: String,
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct ClickCommand {
+    pub subscription_id: SubscriptionID,
+    pub button: Option<u8>,
+    pub command: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize, ValueEnum)]
+pub enum OutputMode {
+    /// JSON of the topic, or the `--format` template.
+    Json,
+    /// Waybar custom module with `"return-type": "json"`.
+    Waybar,
+    /// Eww `(box ...)` literal of workspace buttons, workspaces only.
+    Yuck,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum WorkspaceState {
+    Active,
+    Urgent,
+    Occupied,
+    Empty,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RateLimit {
+    pub subscription_id: SubscriptionID,
+    pub window: RateLimitWindow,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RateLimitWindow {
+    pub interval: u64,
+    pub edge: RateLimitEdge,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum RateLimitEdge {
+    Leading,
+    Trailing,
+    Both,
+}
+
 #[derive(Clone, Copy, PartialEq, Deserialize, Serialize, Subcommand)]
 pub enum CommandOpts {
     #[command(name = "ping", alias = "p")]
@@ -41,21 +167,132 @@ pub enum CommandOpts {
 
     #[command(name = "kill", alias = "k")]
     Kill,
+
+    /// Show daemon version, uptime, listener health and subscribers.
+    #[command(name = "status", alias = "s")]
+    Status {
+        /// Print the raw JSON status.
+        #[arg(long = "json")]
+        json: bool,
+    },
+
+    /// Reload the config file.
+    #[command(name = "reload", alias = "r")]
+    Reload,
 }
 
-#[derive(Clone, Copy, PartialEq, Deserialize, Serialize, Subcommand)]
-pub enum SubscribeOpts {
+// A topic with its own arguments. `O` holds the options of the command using it.
+#[derive(Clone, PartialEq, Deserialize, Serialize, Subcommand)]
+pub enum SubscribeOpts<O: Args = ListenOpts> {
     #[command(name = "workspaces", alias = "ws")]
-    Workspaces { fix_workspace: Option<u32> },
+    Workspaces {
+        fix_workspace: Option<u32>,
+
+        #[command(flatten)]
+        yuck: YuckOpts,
+
+        #[command(flatten)]
+        opts: O,
+    },
 
     #[command(name = "window", alias = "w")]
-    Window { title_length: Option<u32> },
+    Window {
+        title_length: Option<u32>,
+
+        #[command(flatten)]
+        opts: O,
+    },
 
     #[command(name = "wifi", alias = "wf")]
-    Wifi { ssid_length: Option<u32> },
+    Wifi {
+        ssid_length: Option<u32>,
+
+        #[command(flatten)]
+        opts: O,
+    },
 
     #[command(name = "bluetooth", alias = "bt")]
-    Bluetooth,
+    Bluetooth {
+        #[command(flatten)]
+        opts: O,
+    },
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize, Args)]
+pub struct ListenOpts {
+    #[command(flatten)]
+    pub query: QueryOpts,
+
+    /// Exit instead of reconnecting when the daemon goes away.
+    #[arg(long = "no-reconnect")]
+    pub no_reconnect: bool,
+
+    /// Exit when the daemon is stopped on purpose, instead of waiting for it to come back.
+    #[arg(long = "exit-on-shutdown")]
+    pub exit_on_shutdown: bool,
+
+    /// Print this JSON while the daemon is unreachable.
+    #[arg(long = "placeholder")]
+    pub placeholder: Option<String>,
+
+    /// Minimum delay in milliseconds between two updates. Bursts are coalesced to the latest state.
+    #[arg(long = "min-interval", value_name = "MS", default_value_t = 0)]
+    pub min_interval: u32,
+}
+
+// Options of `get`, which listeners take as well.
+#[derive(Clone, PartialEq, Deserialize, Serialize, Args)]
+pub struct QueryOpts {
+    /// Start the daemon in the background if it is not running.
+    #[arg(long = "spawn-daemon")]
+    pub spawn_daemon: bool,
+
+    /// Print each update with a template instead of JSON, e.g. `{icon} {ssid}`.
+    /// See the README for conditionals and filters.
+    #[arg(long = "format", value_name = "TEMPLATE")]
+    pub format: Option<String>,
+
+    /// Output protocol. With `waybar`, `--format` sets the `text` field.
+    #[arg(long = "output", value_enum, default_value_t = OutputMode::Json)]
+    pub output: OutputMode,
+
+    /// Text replacing what truncation cuts off [default: ...].
+    #[arg(long = "ellipsis")]
+    pub ellipsis: Option<String>,
+
+    /// Which part of a long title or SSID to cut off: `start`, `middle` or `end` [default: end].
+    #[arg(long = "ellipsis-position")]
+    pub ellipsis_position: Option<EllipsisPosition>,
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize, Args)]
+pub struct YuckOpts {
+    /// Yuck of a workspace button with `--output yuck`, per state: `active`, `urgent`,
+    /// `occupied` or `empty`. e.g. `urgent=(button :class "urgent" :onclick "{onclick}" "!")`.
+    #[arg(long = "button", value_name = "STATE=TEMPLATE")]
+    pub buttons: Vec<ButtonTemplate>,
+
+    /// Command of a workspace button, filled in as `{onclick}`.
+    #[arg(
+        long = "onclick",
+        value_name = "TEMPLATE",
+        default_value = "hyprctl dispatch workspace {id}"
+    )]
+    pub onclick: String,
+
+    /// Attributes of the box holding the workspace buttons.
+    #[arg(
+        long = "box-attrs",
+        value_name = "ATTRS",
+        default_value = ":class \"workspaces\" :space-evenly false"
+    )]
+    pub box_attrs: String,
+}
+
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct ButtonTemplate {
+    pub state: WorkspaceState,
+    pub template: String,
 }
 
 impl Opts {
@@ -69,24 +306,42 @@ impl From<RawOpts> for Opts {
     fn from(raw_opts: RawOpts) -> Self {
         Opts {
             verbose: raw_opts.verbose,
+            socket: raw_opts.socket,
             action: raw_opts.action,
         }
     }
 }
 
-impl From<CommandOpts> for u8 {
-    fn from(opts: CommandOpts) -> Self {
-        opts as u8
+impl<O: Args> SubscribeOpts<O> {
+    pub fn opts(&self) -> &O {
+        match self {
+            SubscribeOpts::Workspaces { opts, .. }
+            | SubscribeOpts::Window { opts, .. }
+            | SubscribeOpts::Wifi { opts, .. }
+            | SubscribeOpts::Bluetooth { opts } => opts,
+        }
     }
 }
 
-impl TryFrom<u8> for CommandOpts {
-    type Error = HyprvisorError;
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(CommandOpts::Ping),
-            1 => Ok(CommandOpts::Kill),
-            _ => Err(HyprvisorError::ParseError),
+impl AsRef<QueryOpts> for QueryOpts {
+    fn as_ref(&self) -> &QueryOpts {
+        self
+    }
+}
+
+impl AsRef<QueryOpts> for ListenOpts {
+    fn as_ref(&self) -> &QueryOpts {
+        &self.query
+    }
+}
+
+impl From<CommandOpts> for Command {
+    fn from(opts: CommandOpts) -> Self {
+        match opts {
+            CommandOpts::Ping => Command::Ping,
+            CommandOpts::Kill => Command::Kill,
+            CommandOpts::Status { .. } => Command::Status,
+            CommandOpts::Reload => Command::Reload,
         }
     }
 }
@@ -96,6 +351,167 @@ impl Display for CommandOpts {
         match self {
             CommandOpts::Ping => write!(f, "Ping"),
             CommandOpts::Kill => write!(f, "Kill"),
+            CommandOpts::Status { .. } => write!(f, "Status"),
+            CommandOpts::Reload => write!(f, "Reload"),
+        }
+    }
+}
+
+impl RateLimitEdge {
+    pub fn leading(self) -> bool {
+        matches!(self, RateLimitEdge::Leading | RateLimitEdge::Both)
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (topic, window) = value
+            .split_once('=')
+            .ok_or_else(|| "expected <topic>=<ms>[:<edge>]".to_string())?;
+
+        Ok(RateLimit {
+            subscription_id: parse_topic(topic)?,
+            window: window.parse()?,
+        })
+    }
+}
+
+impl FromStr for RateLimitWindow {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (interval, edge) = value.split_once(':').unwrap_or((value, "trailing"));
+
+        let interval = interval
+            .parse()
+            .map_err(|_| format!("invalid interval: {interval}"))?;
+
+        let edge = match edge {
+            "leading" => RateLimitEdge::Leading,
+            "trailing" => RateLimitEdge::Trailing,
+            "both" => RateLimitEdge::Both,
+            _ => return Err(format!("unknown edge: {edge}")),
+        };
+
+        Ok(RateLimitWindow { interval, edge })
+    }
+}
+
+impl FromStr for BlockFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (topic, template) = value
+            .split_once('=')
+            .ok_or_else(|| "expected <topic>=<template>".to_string())?;
+
+        Ok(BlockFormat {
+            subscription_id: parse_topic(topic)?,
+            template: template.to_string(),
+        })
+    }
+}
+
+impl FromStr for ButtonTemplate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (state, template) = value
+            .split_once('=')
+            .ok_or_else(|| "expected <state>=<template>".to_string())?;
+
+        let state = match state {
+            "active" => WorkspaceState::Active,
+            "urgent" => WorkspaceState::Urgent,
+            "occupied" => WorkspaceState::Occupied,
+            "empty" => WorkspaceState::Empty,
+            _ => return Err(format!("unknown workspace state: {state}")),
+        };
+
+        Ok(ButtonTemplate {
+            state,
+            template: template.to_string(),
+        })
+    }
+}
+
+impl FromStr for ClickCommand {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (target, command) = value
+            .split_once('=')
+            .ok_or_else(|| "expected <topic>[:<button>]=<command>".to_string())?;
+
+        let (topic, button) = match target.split_once(':') {
+            Some((topic, button)) => (
+                topic,
+                Some(
+                    button
+                        .parse()
+                        .map_err(|_| format!("invalid button: {button}"))?,
+                ),
+            ),
+            None => (target, None),
+        };
+
+        Ok(ClickCommand {
+            subscription_id: parse_topic(topic)?,
+            button,
+            command: command.to_string(),
+        })
+    }
+}
+
+pub fn parse_topic(topic: &str) -> Result<SubscriptionID, String> {
+    match topic {
+        "workspaces" | "ws" => Ok(SubscriptionID::Workspaces),
+        "window" | "w" => Ok(SubscriptionID::Window),
+        "wifi" | "wf" => Ok(SubscriptionID::Wifi),
+        "bluetooth" | "bt" => Ok(SubscriptionID::Bluetooth),
+        _ => Err(format!("unknown topic: {topic}")),
+    }
+}
+
+impl Display for RateLimitEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateLimitEdge::Leading => write!(f, "leading"),
+            RateLimitEdge::Trailing => write!(f, "trailing"),
+            RateLimitEdge::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl Display for OutputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputMode::Json => write!(f, "json"),
+            OutputMode::Waybar => write!(f, "waybar"),
+            OutputMode::Yuck => write!(f, "yuck"),
+        }
+    }
+}
+
+impl Display for WorkspaceState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkspaceState::Active => write!(f, "active"),
+            WorkspaceState::Urgent => write!(f, "urgent"),
+            WorkspaceState::Occupied => write!(f, "occupied"),
+            WorkspaceState::Empty => write!(f, "empty"),
+        }
+    }
+}
+
+impl Display for OverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverflowPolicy::DropOldest => write!(f, "drop-oldest"),
+            OverflowPolicy::Coalesce => write!(f, "coalesce"),
+            OverflowPolicy::Disconnect => write!(f, "disconnect"),
         }
     }
 }
@@ -0,0 +1,50 @@
+use crate::{application::types::SubscriptionID, error::HyprvisorResult};
+
+use serde::{Deserialize, Serialize};
+
+pub const HEADER: &str = r#"{"version":1,"click_events":true}"#;
+
+#[derive(Serialize)]
+struct Block<'a> {
+    name: String,
+    full_text: &'a str,
+}
+
+#[derive(Deserialize)]
+pub struct ClickEvent {
+    pub name: Option<String>,
+    pub button: Option<u8>,
+}
+
+pub fn block_name(subscription_id: SubscriptionID) -> String {
+    subscription_id.to_string().to_lowercase()
+}
+
+/// One element of the infinite array: the blocks that already have a text.
+pub fn status_line(topics: &[SubscriptionID], texts: &[Option<String>]) -> HyprvisorResult<String> {
+    let blocks: Vec<Block> = topics
+        .iter()
+        .zip(texts)
+        .filter_map(|(&subscription_id, text)| {
+            text.as_deref().map(|full_text| Block {
+                name: block_name(subscription_id),
+                full_text,
+            })
+        })
+        .collect();
+
+    Ok(serde_json::to_string(&blocks)?)
+}
+
+/// Click events come as an infinite array too, one event per line.
+pub fn parse_click_event(line: &str) -> Option<ClickEvent> {
+    let line = line.trim().trim_start_matches(['[', ',']).trim();
+
+    if line.is_empty() {
+        return None;
+    }
+
+    serde_json::from_str(line)
+        .map_err(|e| log::warn!("Invalid click event: {line}. Error: {e}"))
+        .ok()
+}
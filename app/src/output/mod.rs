@@ -0,0 +1,121 @@
+pub mod i3bar;
+pub mod template;
+pub mod waybar;
+pub mod yuck;
+
+pub use template::Template;
+
+use crate::{
+    application::types::SubscriptionID,
+    error::{HyprvisorError, HyprvisorResult},
+    locale::Message,
+    opts::{OutputMode, QueryOpts, SubscribeOpts},
+};
+use yuck::Yuck;
+
+use clap::Args;
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// How a client prints the data of its topic.
+pub struct Output {
+    mode: OutputMode,
+    template: Option<Template>,
+    yuck: Option<Yuck>,
+}
+
+impl Output {
+    pub fn new<O: Args + AsRef<QueryOpts>>(opts: &SubscribeOpts<O>) -> HyprvisorResult<Self> {
+        let query_opts = opts.opts().as_ref();
+
+        let yuck = match (query_opts.output, opts) {
+            (OutputMode::Yuck, SubscribeOpts::Workspaces { yuck, .. }) => Some(Yuck::new(yuck)?),
+            (OutputMode::Yuck, _) => {
+                return Err(HyprvisorError::UnsupportedOutput(
+                    "yuck is only available for workspaces".to_string(),
+                ))
+            }
+            _ => None,
+        };
+
+        Ok(Output {
+            mode: query_opts.output,
+            template: query_opts
+                .format
+                .as_deref()
+                .map(Template::parse)
+                .transpose()?,
+            yuck,
+        })
+    }
+
+    pub fn render(
+        &self,
+        subscription_id: SubscriptionID,
+        value: &Value,
+    ) -> HyprvisorResult<String> {
+        let value = localized(subscription_id, value);
+        let text = self
+            .template
+            .as_ref()
+            .map(|template| template.render(&value));
+
+        match self.mode {
+            OutputMode::Json => match text {
+                Some(text) => Ok(text),
+                None => Ok(serde_json::to_string(&value)?),
+            },
+            OutputMode::Waybar => waybar::render(subscription_id, &value, text),
+            OutputMode::Yuck => Ok(self
+                .yuck
+                .as_ref()
+                .map(|yuck| yuck.render(&value))
+                .unwrap_or_default()),
+        }
+    }
+}
+
+/// Topic data as clients print it: bluetooth devices without a name are
+/// named in the language of the client.
+pub fn localized(subscription_id: SubscriptionID, value: &Value) -> Cow<'_, Value> {
+    if subscription_id != SubscriptionID::Bluetooth {
+        return Cow::Borrowed(value);
+    }
+
+    let mut value = value.clone();
+
+    for device in value
+        .get_mut("connected_devices")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+    {
+        if device["name"].as_str().is_some_and(str::is_empty) {
+            device["name"] = Message::UnknownDevice.to_string().into();
+        }
+    }
+
+    Cow::Owned(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn names_unknown_devices() {
+        let value = json!({"powered": true, "connected_devices": [{"name": ""}, {"name": "Buds"}]});
+        let localized = localized(SubscriptionID::Bluetooth, &value);
+
+        assert_eq!(
+            localized["connected_devices"][0]["name"],
+            Message::UnknownDevice.to_string()
+        );
+        assert_eq!(localized["connected_devices"][1]["name"], "Buds");
+
+        let value = json!({"class": "", "title": ""});
+        assert_eq!(*super::localized(SubscriptionID::Window, &value), value);
+    }
+}
@@ -0,0 +1,435 @@
+use crate::{
+    application::{format::truncate, types::EllipsisPosition},
+    error::{HyprvisorError, HyprvisorResult},
+};
+
+use serde_json::Value;
+
+/// Text template rendered against the JSON of a topic.
+///
+/// - `{field}`, `{a.b}`, `{list.0}` print a value, `{.}` prints the current one.
+/// - `{field:.40}` keeps at most 40 columns of the field.
+/// - `{field|upper}` applies filters: `upper`, `lower`, `trim`, `len`,
+///   `default:<text>` and `join:<separator>`.
+/// - `{?field}...{/field}` renders when the field is set (non-empty, non-zero, true),
+///   `{?field=value}...{/field}` when it equals `value`, `{!field}...{/field}` otherwise.
+/// - `{#field}...{/field}` renders once per element of a list.
+/// - `{{` and `}}` print literal braces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Field(Field),
+    Section(Section),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    path: String,
+    precision: Option<usize>,
+    filters: Vec<Filter>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Filter {
+    Upper,
+    Lower,
+    Trim,
+    Len,
+    Default(String),
+    Join(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Section {
+    kind: SectionKind,
+    path: String,
+    expected: Option<String>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SectionKind {
+    If,
+    Unless,
+    Each,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> HyprvisorResult<Self> {
+        let mut chars = source.chars().peekable();
+        let mut stack: Vec<Section> = Vec::new();
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(invalid("unmatched '}'")),
+                '{' => {
+                    let mut tag = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => return Err(invalid("unclosed '{'")),
+                            Some(c) => tag.push(c),
+                        }
+                    }
+
+                    let current = stack.last_mut().map_or(&mut nodes, |s| &mut s.nodes);
+                    if !text.is_empty() {
+                        current.push(Node::Text(std::mem::take(&mut text)));
+                    }
+
+                    match tag.chars().next() {
+                        Some('?') => stack.push(Section::open(SectionKind::If, &tag[1..])?),
+                        Some('!') => stack.push(Section::open(SectionKind::Unless, &tag[1..])?),
+                        Some('#') => stack.push(Section::open(SectionKind::Each, &tag[1..])?),
+                        Some('/') => {
+                            let section = stack
+                                .pop()
+                                .ok_or_else(|| invalid(&format!("unexpected {{{tag}}}")))?;
+
+                            if section.path != tag[1..].trim() {
+                                return Err(invalid(&format!(
+                                    "{{{tag}}} closes a section on '{}'",
+                                    section.path
+                                )));
+                            }
+
+                            stack
+                                .last_mut()
+                                .map_or(&mut nodes, |s| &mut s.nodes)
+                                .push(Node::Section(section));
+                        }
+                        _ => current.push(Node::Field(Field::parse(&tag)?)),
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        if let Some(section) = stack.last() {
+            return Err(invalid(&format!(
+                "section on '{}' is not closed",
+                section.path
+            )));
+        }
+
+        if !text.is_empty() {
+            nodes.push(Node::Text(text));
+        }
+
+        Ok(Template { nodes })
+    }
+
+    pub fn render(&self, value: &Value) -> String {
+        let mut output = String::new();
+        render_nodes(&self.nodes, &mut vec![value], &mut output);
+        output
+    }
+}
+
+impl Section {
+    fn open(kind: SectionKind, tag: &str) -> HyprvisorResult<Self> {
+        let (path, expected) = match tag.split_once('=') {
+            Some((path, expected)) if kind != SectionKind::Each => {
+                (path.trim(), Some(expected.to_string()))
+            }
+            Some(_) => return Err(invalid("a list section cannot compare values")),
+            None => (tag.trim(), None),
+        };
+
+        if path.is_empty() {
+            return Err(invalid("section without a field"));
+        }
+
+        Ok(Section {
+            kind,
+            path: path.to_string(),
+            expected,
+            nodes: Vec::new(),
+        })
+    }
+}
+
+impl Field {
+    fn parse(tag: &str) -> HyprvisorResult<Self> {
+        let mut parts = tag.split('|');
+        let head = parts.next().unwrap_or_default();
+
+        let (path, precision) = match head.split_once(':') {
+            Some((path, spec)) => {
+                let precision = spec
+                    .strip_prefix('.')
+                    .and_then(|precision| precision.parse().ok())
+                    .ok_or_else(|| invalid(&format!("unknown format spec '{spec}'")))?;
+                (path.trim(), Some(precision))
+            }
+            None => (head.trim(), None),
+        };
+
+        if path.is_empty() {
+            return Err(invalid("empty field"));
+        }
+
+        let filters = parts.map(Filter::parse).collect::<HyprvisorResult<_>>()?;
+
+        Ok(Field {
+            path: path.to_string(),
+            precision,
+            filters,
+        })
+    }
+}
+
+impl Filter {
+    fn parse(filter: &str) -> HyprvisorResult<Self> {
+        let (name, argument) = filter.split_once(':').unwrap_or((filter, ""));
+
+        match name.trim() {
+            "upper" => Ok(Filter::Upper),
+            "lower" => Ok(Filter::Lower),
+            "trim" => Ok(Filter::Trim),
+            "len" => Ok(Filter::Len),
+            "default" => Ok(Filter::Default(argument.to_string())),
+            "join" => Ok(Filter::Join(argument.to_string())),
+            name => Err(invalid(&format!("unknown filter '{name}'"))),
+        }
+    }
+}
+
+fn render_nodes<'a>(nodes: &'a [Node], scopes: &mut Vec<&'a Value>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Field(field) => {
+                output.push_str(&render_field(field, lookup(scopes, &field.path)))
+            }
+            Node::Section(section) => {
+                let value = lookup(scopes, &section.path);
+
+                match section.kind {
+                    SectionKind::Each => {
+                        for item in value.and_then(Value::as_array).into_iter().flatten() {
+                            scopes.push(item);
+                            render_nodes(&section.nodes, scopes, output);
+                            scopes.pop();
+                        }
+                    }
+                    kind => {
+                        let matched = match &section.expected {
+                            Some(expected) => value.map(to_text).as_ref() == Some(expected),
+                            None => value.is_some_and(is_truthy),
+                        };
+
+                        if matched == (kind == SectionKind::If) {
+                            render_nodes(&section.nodes, scopes, output);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_field(field: &Field, value: Option<&Value>) -> String {
+    let mut text = value.map(to_text).unwrap_or_default();
+
+    for filter in &field.filters {
+        text = match filter {
+            Filter::Upper => text.to_uppercase(),
+            Filter::Lower => text.to_lowercase(),
+            Filter::Trim => text.trim().to_string(),
+            Filter::Len => match value {
+                Some(Value::Array(items)) => items.len(),
+                Some(Value::Object(fields)) => fields.len(),
+                _ => text.chars().count(),
+            }
+            .to_string(),
+            Filter::Default(default) if text.is_empty() => default.clone(),
+            Filter::Default(_) => text,
+            Filter::Join(separator) => match value {
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .map(to_text)
+                    .collect::<Vec<_>>()
+                    .join(separator),
+                _ => text,
+            },
+        };
+    }
+
+    match field.precision {
+        Some(precision) => truncate(&text, precision, "", EllipsisPosition::End),
+        None => text,
+    }
+}
+
+/// Resolve a dotted path, starting from the innermost list item.
+fn lookup<'a>(scopes: &[&'a Value], path: &str) -> Option<&'a Value> {
+    if path == "." {
+        return scopes.last().copied();
+    }
+
+    let mut segments = path.split('.');
+    let first = segments.next()?;
+
+    let mut value = scopes.iter().rev().find_map(|scope| child(scope, first))?;
+
+    for segment in segments {
+        value = child(value, segment)?;
+    }
+
+    Some(value)
+}
+
+fn child<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(fields) => fields.get(key),
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => None,
+    }
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(to_text).collect::<Vec<_>>().join(" "),
+        value => value.to_string(),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().is_some_and(|number| number != 0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+fn invalid(reason: &str) -> HyprvisorError {
+    HyprvisorError::InvalidTemplate(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn render(source: &str, value: Value) -> String {
+        Template::parse(source).unwrap().render(&value)
+    }
+
+    fn parse_error(source: &str) -> String {
+        match Template::parse(source) {
+            Err(HyprvisorError::InvalidTemplate(reason)) => reason,
+            result => panic!("{source:?} should not parse, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn fields_and_paths() {
+        let value = json!({"class": "kitty", "window": {"title": "nvim"}, "list": [1, 2]});
+
+        assert_eq!(
+            render("{class}: {window.title}", value.clone()),
+            "kitty: nvim"
+        );
+        assert_eq!(render("{list.1} {list}", value.clone()), "2 1 2");
+        assert_eq!(render("{#list}[{.}]{/list}", value), "[1][2]");
+    }
+
+    #[test]
+    fn missing_fields_render_empty() {
+        let value = json!({"class": "kitty"});
+
+        assert_eq!(render("<{title}>", value.clone()), "<>");
+        assert_eq!(render("<{class.name}>", value.clone()), "<>");
+        assert_eq!(render("<{list.3}>", value.clone()), "<>");
+        assert_eq!(
+            render("{?title}set{/title}{!title}unset{/title}", value.clone()),
+            "unset"
+        );
+        assert_eq!(render("{#devices}x{/devices}", value), "");
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(render("{{{class}}}", json!({"class": "kitty"})), "{kitty}");
+        assert_eq!(render("{{}}", json!({})), "{}");
+    }
+
+    #[test]
+    fn filters() {
+        let value = json!({"title": "  Nvim  ", "empty": "", "tags": ["a", "b"], "ssid": "Home"});
+
+        assert_eq!(render("{title|trim|upper}", value.clone()), "NVIM");
+        assert_eq!(render("{title|trim|lower}", value.clone()), "nvim");
+        assert_eq!(render("{tags|len} {ssid|len}", value.clone()), "2 4");
+        assert_eq!(
+            render("{empty|default:none} {ssid|default:none}", value.clone()),
+            "none Home"
+        );
+        assert_eq!(render("{tags|join:, }", value.clone()), "a, b");
+        assert_eq!(render("{title:.3|trim}", value), "Nvi");
+    }
+
+    #[test]
+    fn sections() {
+        let value = json!({"powered": true, "state": "connected", "signal": 0,
+            "devices": [{"name": "Buds"}, {"name": "Mouse"}]});
+
+        assert_eq!(render("{?powered}on{/powered}", value.clone()), "on");
+        assert_eq!(
+            render("{!signal}no signal{/signal}", value.clone()),
+            "no signal"
+        );
+        assert_eq!(render("{?state=connected}up{/state}", value.clone()), "up");
+        assert_eq!(render("{?state=disabled}off{/state}", value.clone()), "");
+        assert_eq!(
+            render("{#devices}{name}{?powered}!{/powered} {/devices}", value),
+            "Buds! Mouse! "
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_error("{class"), "unclosed '{'");
+        assert_eq!(parse_error("{a{b}}"), "unclosed '{'");
+        assert_eq!(parse_error("class}"), "unmatched '}'");
+        assert_eq!(
+            parse_error("{?powered}on"),
+            "section on 'powered' is not closed"
+        );
+        assert_eq!(
+            parse_error("{?powered}{#devices}{/powered}{/devices}"),
+            "{/powered} closes a section on 'devices'"
+        );
+        assert_eq!(parse_error("{/powered}"), "unexpected {/powered}");
+        assert_eq!(
+            parse_error("{#devices=x}{/devices}"),
+            "a list section cannot compare values"
+        );
+        assert_eq!(parse_error("{?}{/}"), "section without a field");
+        assert_eq!(parse_error("{}"), "empty field");
+        assert_eq!(parse_error("{title:40}"), "unknown format spec '40'");
+        assert_eq!(parse_error("{title|reverse}"), "unknown filter 'reverse'");
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    application::types::SubscriptionID,
+    bluetooth::types::{BluetoothDeviceInfo, BluetoothInfo},
+    error::{ClientError, HyprvisorError, HyprvisorResult},
+    hyprland::types::{HyprWindowInfo, HyprWorkspaceInfo},
+    locale::Message,
+    wifi::types::{WifiInfo, WifiState},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Object expected by a Waybar custom module with `"return-type": "json"`.
+#[derive(Serialize)]
+struct WaybarBlock {
+    text: String,
+    alt: String,
+    tooltip: String,
+    class: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentage: Option<u8>,
+}
+
+/// Render a topic as a Waybar block. `text` replaces the default text when set.
+/// Waybar parses the strings as Pango markup, so they are escaped.
+pub fn render(
+    subscription_id: SubscriptionID,
+    value: &Value,
+    text: Option<String>,
+) -> HyprvisorResult<String> {
+    let mut block = block(subscription_id, value)?;
+
+    if let Some(text) = text {
+        block.text = text;
+    }
+
+    for field in [
+        &mut block.text,
+        &mut block.alt,
+        &mut block.tooltip,
+        &mut block.class,
+    ] {
+        *field = escape_markup(field);
+    }
+
+    Ok(serde_json::to_string(&block)?)
+}
+
+/// Short human readable text of a topic.
+pub fn text(subscription_id: SubscriptionID, value: &Value) -> HyprvisorResult<String> {
+    block(subscription_id, value).map(|block| block.text)
+}
+
+fn block(subscription_id: SubscriptionID, value: &Value) -> HyprvisorResult<WaybarBlock> {
+    match subscription_id {
+        SubscriptionID::Workspaces => Ok(workspaces(serde_json::from_value(value.clone())?)),
+        SubscriptionID::Window => Ok(window(serde_json::from_value(value.clone())?)),
+        SubscriptionID::Wifi => Ok(wifi(serde_json::from_value(value.clone())?)),
+        SubscriptionID::Bluetooth => Ok(bluetooth(serde_json::from_value(value.clone())?)),
+        SubscriptionID::Invalid => Err(HyprvisorError::Client(ClientError::InvalidSubscription)),
+    }
+}
+
+fn workspaces(workspaces: Vec<HyprWorkspaceInfo>) -> WaybarBlock {
+    let active = workspaces
+        .iter()
+        .find(|ws| ws.active)
+        .map(|ws| ws.id.to_string())
+        .unwrap_or_default();
+
+    let occupied: Vec<String> = workspaces
+        .iter()
+        .filter(|ws| ws.occupied)
+        .map(|ws| ws.id.to_string())
+        .collect();
+
+    WaybarBlock {
+        text: workspaces
+            .iter()
+            .map(|ws| match ws.active {
+                true => format!("[{}]", ws.id),
+                false => ws.id.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        tooltip: format!(
+            "{}: {active}\n{}: {}",
+            Message::Active,
+            Message::Occupied,
+            occupied.join(", ")
+        ),
+        alt: active,
+        class: "workspaces".to_string(),
+        percentage: None,
+    }
+}
+
+fn window(window: HyprWindowInfo) -> WaybarBlock {
+    let class = window
+        .class
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+
+    WaybarBlock {
+        tooltip: match window.class.is_empty() {
+            true => window.title.clone(),
+            false => format!("{}: {}", window.class, window.title),
+        },
+        text: window.title,
+        alt: window.class,
+        class,
+        percentage: None,
+    }
+}
+
+fn wifi(wifi: WifiInfo) -> WaybarBlock {
+    let state = serde_json::to_value(&wifi.state)
+        .ok()
+        .and_then(|state| state.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    WaybarBlock {
+        text: match wifi.ssid.is_empty() {
+            true => wifi.icon.clone(),
+            false => format!("{} {}", wifi.icon, wifi.ssid),
+        },
+        tooltip: match wifi.state {
+            WifiState::Connected | WifiState::Connecting => wifi.ssid,
+            _ => Message::WifiState(&wifi.state).to_string(),
+        },
+        percentage: (wifi.state == WifiState::Connected).then_some(wifi.signal),
+        alt: state.clone(),
+        class: state,
+    }
+}
+
+fn bluetooth(bluetooth: BluetoothInfo) -> WaybarBlock {
+    let (state, text, tooltip) = match (bluetooth.powered, bluetooth.connected_devices.is_empty()) {
+        (false, _) => ("off", Message::Off, Message::BluetoothOff),
+        (true, true) => ("on", Message::On, Message::BluetoothOn),
+        (true, false) => ("connected", Message::Connected, Message::BluetoothOn),
+    };
+
+    WaybarBlock {
+        text: match bluetooth.connected_devices.as_slice() {
+            [] => text.to_string(),
+            [device] => device_name(device),
+            devices => Message::Devices(devices.len()).to_string(),
+        },
+        tooltip: match bluetooth.connected_devices.is_empty() {
+            true => tooltip.to_string(),
+            false => bluetooth
+                .connected_devices
+                .iter()
+                .map(|device| format!("{} ({})", device_name(device), device.address))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        },
+        alt: state.to_string(),
+        class: state.to_string(),
+        percentage: None,
+    }
+}
+
+fn device_name(device: &BluetoothDeviceInfo) -> String {
+    match device.name.is_empty() {
+        true => Message::UnknownDevice.to_string(),
+        false => device.name.clone(),
+    }
+}
+
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn escapes_markup_in_every_field() {
+        let value = json!({"class": "a&b", "title": "<b>\"Tom's\" & Jerry</b>"});
+        let block: Value =
+            serde_json::from_str(&render(SubscriptionID::Window, &value, None).unwrap()).unwrap();
+
+        assert_eq!(
+            block["text"],
+            "&lt;b&gt;&quot;Tom&#39;s&quot; &amp; Jerry&lt;/b&gt;"
+        );
+        assert_eq!(block["alt"], "a&amp;b");
+        assert_eq!(
+            block["tooltip"],
+            "a&amp;b: &lt;b&gt;&quot;Tom&#39;s&quot; &amp; Jerry&lt;/b&gt;"
+        );
+
+        let block: Value = serde_json::from_str(
+            &render(SubscriptionID::Window, &value, Some("R&D".to_string())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(block["text"], "R&amp;D");
+    }
+
+    #[test]
+    fn names_unknown_devices_in_the_client_language() {
+        let value = json!({"powered": true, "icon": "", "connected_devices": [
+            {"name": "", "address": "01:02:03:04:05:06", "kind": "", "icon": ""}
+        ]});
+
+        assert_eq!(
+            text(SubscriptionID::Bluetooth, &value).unwrap(),
+            Message::UnknownDevice.to_string()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use super::Template;
+use crate::{
+    error::HyprvisorResult,
+    opts::{WorkspaceState, YuckOpts},
+};
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+const DEFAULT_BUTTON: &str = r#"(button :class "workspace {state}" :onclick "{onclick}" "{id}")"#;
+
+/// Eww literal of the workspaces, one button per workspace, for `(literal :content ...)`.
+pub struct Yuck {
+    box_attrs: String,
+    buttons: HashMap<WorkspaceState, Template>,
+    default_button: Template,
+    onclick: Template,
+}
+
+impl Yuck {
+    pub fn new(opts: &YuckOpts) -> HyprvisorResult<Self> {
+        Ok(Yuck {
+            box_attrs: opts.box_attrs.clone(),
+            buttons: opts
+                .buttons
+                .iter()
+                .map(|button| Ok((button.state, Template::parse(&button.template)?)))
+                .collect::<HyprvisorResult<_>>()?,
+            default_button: Template::parse(DEFAULT_BUTTON)?,
+            onclick: Template::parse(&opts.onclick)?,
+        })
+    }
+
+    /// A whole `(box ...)` on a single line, as `deflisten` reads one value per line.
+    pub fn render(&self, workspaces: &Value) -> String {
+        let buttons: String = workspaces
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|workspace| format!(" {}", self.button(workspace)))
+            .collect();
+
+        format!("(box {}{buttons})", self.box_attrs)
+    }
+
+    /// Fields are escaped for yuck strings, `{state}` and `{onclick}` are added.
+    fn button(&self, workspace: &Value) -> String {
+        let state = state(workspace);
+
+        let mut fields = escape_strings(workspace);
+        if let Value::Object(fields) = &mut fields {
+            fields.insert("state".to_string(), state.to_string().into());
+            fields.insert(
+                "onclick".to_string(),
+                escape(&self.onclick.render(workspace)).into(),
+            );
+        }
+
+        self.buttons
+            .get(&state)
+            .unwrap_or(&self.default_button)
+            .render(&fields)
+            .replace('\n', " ")
+    }
+}
+
+fn state(workspace: &Value) -> WorkspaceState {
+    let is = |field: &str| workspace[field].as_bool().unwrap_or_default();
+
+    match (is("active"), is("urgent"), is("occupied")) {
+        (true, _, _) => WorkspaceState::Active,
+        (false, true, _) => WorkspaceState::Urgent,
+        (false, false, true) => WorkspaceState::Occupied,
+        (false, false, false) => WorkspaceState::Empty,
+    }
+}
+
+fn escape_strings(value: &Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(escape(text)),
+        Value::Array(items) => Value::Array(items.iter().map(escape_strings).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), escape_strings(value)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -1,43 +1,61 @@
 use super::{
     types::{WifiInfo, WifiState},
-    CURRENT_WIFI, MAX_ATTEMPT_RETRY, POLLING_INTERVAL,
+    CURRENT_WIFI,
 };
 use crate::{
-    application::types::SubscriptionID,
+    application::{
+        broadcast, status,
+        types::{ListenerState, ModuleID, SubscriptionID},
+    },
+    config::config,
     error::{HyprvisorError, HyprvisorResult},
-    global::SUBSCRIBERS,
-    ipc::{message::HyprvisorMessage, HyprvisorWriteSock},
-    wifi::REBOOT_IWD_DELAY,
 };
 
+use hyprvisor_client::ipc::message::HyprvisorMessage;
 use iwdrs::{modes::Mode, session::Session, station::Station};
-use std::{thread::sleep, time::Duration};
-use tokio::net::UnixStream;
+use std::time::Duration;
+use tokio::time::sleep;
 
 pub async fn start_wifi_listener() -> HyprvisorResult<()> {
     log::debug!("Start wifi listener");
 
-    for attempt in 0..MAX_ATTEMPT_RETRY {
+    // Forget what a previous run saw, so that its first poll is published.
+    *CURRENT_WIFI.lock().await = WifiInfo::default();
+
+    let max_attempt = config().wifi.max_retries;
+
+    for attempt in 0..max_attempt {
         log::info!(
             "Attemp to start wifi listener: {}/{}",
             attempt + 1,
-            MAX_ATTEMPT_RETRY
+            max_attempt
         );
+        status::set_listener_state(
+            ModuleID::Wifi,
+            match attempt {
+                0 => ListenerState::Starting,
+                _ => ListenerState::Restarting {
+                    attempt: attempt + 1,
+                    max_attempt,
+                },
+            },
+        );
+
         let _ = connect_to_iwd_session().await;
 
         log::warn!("Iwd is down. Rebooting...");
-        sleep(Duration::from_millis(REBOOT_IWD_DELAY));
+        sleep(Duration::from_millis(config().wifi.retry_delay)).await;
     }
 
     log::error!("Cannot start wifi listener. Out of attempt.");
+    status::set_listener_state(ModuleID::Wifi, ListenerState::GaveUp);
     Err(HyprvisorError::WifiError)
 }
 
-pub async fn response_to_subscription(stream: &UnixStream) -> HyprvisorResult<()> {
+pub async fn snapshot() -> HyprvisorResult<HyprvisorMessage> {
     let current_wifi = CURRENT_WIFI.lock().await;
 
-    let init_message: HyprvisorMessage = HyprvisorMessage::try_from((*current_wifi).clone())?;
-    stream.write_message(init_message).await.map(|_| ())
+    Ok(HyprvisorMessage::try_from((*current_wifi).clone())?)
 }
 
 async fn connect_to_iwd_session() -> HyprvisorResult<()> {
@@ -79,6 +97,8 @@ async fn connect_to_iwd_session() -> HyprvisorResult<()> {
         }
     };
 
+    status::set_listener_state(ModuleID::Wifi, ListenerState::Running);
+
     polling_data(station).await
 }
 
@@ -87,38 +107,53 @@ async fn polling_data(station: Station) -> HyprvisorResult<()> {
         let wifi_info = match station.state().await {
             Ok(state) => {
                 let mut ssid = String::new();
+                let mut signal = 0;
 
                 if state == "connected" {
                     ssid = match station.connected_network().await {
                         Ok(Some(network)) => network.name().await.unwrap_or_else(|_| String::new()),
                         _ => String::new(),
-                    }
+                    };
+                    signal = signal_quality(&station, &ssid).await;
                 };
 
-                let wifi_state = WifiState::from(state.as_str());
-
                 WifiInfo {
-                    state: wifi_state.clone(),
+                    state: WifiState::from(state.as_str()),
                     ssid,
-                    icon: WifiInfo::get_wifi_icon(wifi_state),
+                    signal,
+                    icon: String::new(),
                 }
             }
             Err(_) => {
                 log::error!("Cannot get iwd state.");
                 WifiInfo {
                     state: WifiState::Disabled,
-                    ssid: String::new(),
-                    icon: WifiInfo::get_wifi_icon(WifiState::Disabled),
+                    ..WifiInfo::default()
                 }
             }
         };
 
         handle_wifi_info(wifi_info).await?;
 
-        sleep(Duration::from_millis(POLLING_INTERVAL));
+        sleep(Duration::from_millis(config().wifi.polling_interval)).await;
     }
 }
 
+/// iwd reports the strength in 100 * dBm. -100 dBm and below is 0%, -50 dBm and above is 100%.
+async fn signal_quality(station: &Station, ssid: &str) -> u8 {
+    let Ok(networks) = station.discovered_networks().await else {
+        return 0;
+    };
+
+    for (network, strength) in networks {
+        if network.name().await.is_ok_and(|name| name == ssid) {
+            return (2 * (i32::from(strength) / 100 + 100)).clamp(0, 100) as u8;
+        }
+    }
+
+    0
+}
+
 async fn handle_wifi_info(wifi_info: WifiInfo) -> HyprvisorResult<()> {
     let mut current_wifi = CURRENT_WIFI.lock().await;
 
@@ -128,7 +163,7 @@ async fn handle_wifi_info(wifi_info: WifiInfo) -> HyprvisorResult<()> {
 
     *current_wifi = wifi_info.clone();
 
-    let _ = broadcast_info(wifi_info).await;
+    let _ = broadcast_info(wifi_info);
 
     if current_wifi.state == WifiState::Disabled {
         return Err(HyprvisorError::WifiError);
@@ -137,31 +172,6 @@ async fn handle_wifi_info(wifi_info: WifiInfo) -> HyprvisorResult<()> {
     Ok(())
 }
 
-async fn broadcast_info(wifi_info: WifiInfo) -> HyprvisorResult<()> {
-    let mut subscribers_ref = SUBSCRIBERS.lock().await;
-
-    let subscribers = match subscribers_ref.get_mut(&SubscriptionID::Wifi) {
-        Some(subs) if !subs.is_empty() => subs,
-        Some(_) | None => {
-            return Err(HyprvisorError::NoSubscriber);
-        }
-    };
-
-    let message: HyprvisorMessage = HyprvisorMessage::try_from(wifi_info)?;
-
-    let mut disconnected_pid = Vec::new();
-
-    for (pid, stream) in subscribers.iter_mut() {
-        if stream.try_write_message(&message, 2).await.is_err() {
-            log::debug!("Client {pid} is disconnected.");
-            disconnected_pid.push(*pid);
-        }
-    }
-
-    for pid in disconnected_pid {
-        log::info!("Remove {pid}");
-        subscribers.remove(&pid);
-    }
-
-    Ok(())
+fn broadcast_info(wifi_info: WifiInfo) -> HyprvisorResult<()> {
+    broadcast::publish(SubscriptionID::Wifi, HyprvisorMessage::try_from(wifi_info)?)
 }
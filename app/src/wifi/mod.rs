@@ -1,7 +1,7 @@
 pub mod listener;
 pub mod types;
 
-pub use listener::response_to_subscription;
+pub use listener::snapshot;
 pub use listener::start_wifi_listener;
 
 use crate::wifi::types::WifiInfo;
@@ -10,9 +10,5 @@ use once_cell::sync::Lazy;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-const POLLING_INTERVAL: u64 = 500;
-const REBOOT_IWD_DELAY: u64 = 2500;
-const MAX_ATTEMPT_RETRY: usize = 10;
-
 static CURRENT_WIFI: Lazy<Arc<Mutex<WifiInfo>>> =
     Lazy::new(|| Arc::new(Mutex::new(WifiInfo::default())));
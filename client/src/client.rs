@@ -0,0 +1,172 @@
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    ipc::{
+        connect_to_socket,
+        message::{HyprvisorMessage, MessageType},
+        HyprvisorReadSock, HyprvisorRequestResponse, HyprvisorWriteSock,
+    },
+    socket::default_socket_path,
+    types::{
+        BluetoothInfo, ClientInfo, Command, DaemonStatus, HyprWindowInfo, HyprWorkspaceInfo,
+        SubscriptionID, SubscriptionOptions, WifiInfo,
+    },
+};
+
+use futures::{stream, Stream};
+use tokio::net::UnixStream;
+
+const CONNECT_ATTEMPT: u8 = 3;
+const CONNECT_DELAY: u64 = 100;
+
+/// Data published on a subscription topic.
+pub trait Topic: TryFrom<HyprvisorMessage, Error = HyprvisorError> {
+    const ID: SubscriptionID;
+}
+
+impl Topic for Vec<HyprWorkspaceInfo> {
+    const ID: SubscriptionID = SubscriptionID::Workspaces;
+}
+
+impl Topic for HyprWindowInfo {
+    const ID: SubscriptionID = SubscriptionID::Window;
+}
+
+impl Topic for WifiInfo {
+    const ID: SubscriptionID = SubscriptionID::Wifi;
+}
+
+impl Topic for BluetoothInfo {
+    const ID: SubscriptionID = SubscriptionID::Bluetooth;
+}
+
+/// Connection settings for a hyprvisor daemon.
+#[derive(Clone, Debug)]
+pub struct Client {
+    socket: String,
+}
+
+impl Client {
+    /// Client for the daemon of the current Hyprland session.
+    pub fn from_env() -> HyprvisorResult<Self> {
+        Ok(Client {
+            socket: default_socket_path()?,
+        })
+    }
+
+    pub fn with_socket(socket: &str) -> Self {
+        Client {
+            socket: socket.to_string(),
+        }
+    }
+
+    pub fn socket(&self) -> &str {
+        &self.socket
+    }
+
+    pub async fn ping(&self) -> HyprvisorResult<()> {
+        self.send_command(Command::Ping).await.map(|_| ())
+    }
+
+    pub async fn kill(&self) -> HyprvisorResult<()> {
+        self.send_command(Command::Kill).await.map(|_| ())
+    }
+
+    /// Reload the config file of the daemon. Returns its answer.
+    pub async fn reload(&self) -> HyprvisorResult<String> {
+        self.send_command(Command::Reload).await
+    }
+
+    /// Version, uptime, listener health and subscribers of the daemon.
+    pub async fn status(&self) -> HyprvisorResult<DaemonStatus> {
+        Ok(serde_json::from_str(
+            &self.send_command(Command::Status).await?,
+        )?)
+    }
+
+    /// Current value of a topic.
+    pub async fn get<T: Topic>(&self) -> HyprvisorResult<T> {
+        self.get_with(SubscriptionOptions::default()).await
+    }
+
+    /// Current value of a topic, shaped by the daemon with `options`.
+    pub async fn get_with<T: Topic>(&self, options: SubscriptionOptions) -> HyprvisorResult<T> {
+        let stream = self.connect().await?;
+        let query = HyprvisorMessage::query(&ClientInfo::with_options(T::ID, options))?;
+        let response = stream.try_send_and_receive_message(&query, 3).await?;
+
+        T::try_from(response)
+    }
+
+    /// Stream of values of a topic, starting with the current one.
+    /// The stream ends when the daemon stops or the connection is lost.
+    pub async fn subscribe<T: Topic>(&self) -> HyprvisorResult<impl Stream<Item = T>> {
+        self.subscribe_with(SubscriptionOptions::default()).await
+    }
+
+    /// Same as [`Client::subscribe`], with every value shaped by the daemon with `options`.
+    pub async fn subscribe_with<T: Topic>(
+        &self,
+        options: SubscriptionOptions,
+    ) -> HyprvisorResult<impl Stream<Item = T>> {
+        let stream = self.connect().await?;
+        let subscription = HyprvisorMessage::try_from(&ClientInfo::with_options(T::ID, options))?;
+        stream.try_write_message(&subscription, 3).await?;
+
+        Ok(stream::unfold(stream, |stream| async move {
+            let message = match stream.read_message().await {
+                Ok(message) if message.message_type == MessageType::Response => message,
+                Ok(_) => return None,
+                Err(e) => {
+                    log::warn!("Subscription to {} ended: {e}", T::ID);
+                    return None;
+                }
+            };
+
+            match T::try_from(message) {
+                Ok(value) => Some((value, stream)),
+                Err(e) => {
+                    log::warn!("Invalid message on {}: {e}", T::ID);
+                    None
+                }
+            }
+        }))
+    }
+
+    pub async fn subscribe_workspaces(
+        &self,
+    ) -> HyprvisorResult<impl Stream<Item = Vec<HyprWorkspaceInfo>>> {
+        self.subscribe().await
+    }
+
+    pub async fn subscribe_window(&self) -> HyprvisorResult<impl Stream<Item = HyprWindowInfo>> {
+        self.subscribe().await
+    }
+
+    pub async fn subscribe_wifi(&self) -> HyprvisorResult<impl Stream<Item = WifiInfo>> {
+        self.subscribe().await
+    }
+
+    pub async fn subscribe_bluetooth(&self) -> HyprvisorResult<impl Stream<Item = BluetoothInfo>> {
+        self.subscribe().await
+    }
+
+    async fn connect(&self) -> HyprvisorResult<UnixStream> {
+        connect_to_socket(&self.socket, CONNECT_ATTEMPT, CONNECT_DELAY)
+            .await
+            .map_err(|_| HyprvisorError::NoDaemon)
+    }
+
+    async fn send_command(&self, command: Command) -> HyprvisorResult<String> {
+        let response = self
+            .connect()
+            .await?
+            .send_and_receive_message(command.into())
+            .await?;
+
+        if response.message_type != MessageType::Response || !response.is_valid() {
+            return Err(HyprvisorError::InvalidResponse);
+        }
+
+        String::from_utf8(response.payload).map_err(|_| HyprvisorError::ParseError)
+    }
+}
@@ -0,0 +1,54 @@
+use std::{fmt::Display, io, result::Result};
+
+pub type HyprvisorResult<T> = Result<T, HyprvisorError>;
+
+/// Errors talking to the daemon. New variants are not a breaking change.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HyprvisorError {
+    NoDaemon,
+    JsonError(serde_json::Error),
+    BincodeError(bincode::Error),
+    IoError(io::Error),
+    IpcError,
+    ParseError,
+    InvalidMessage,
+    InvalidResponse,
+    InvalidSubscription,
+    NoHyprland,
+}
+
+impl From<io::Error> for HyprvisorError {
+    fn from(err: io::Error) -> Self {
+        HyprvisorError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for HyprvisorError {
+    fn from(err: serde_json::Error) -> Self {
+        HyprvisorError::JsonError(err)
+    }
+}
+
+impl From<bincode::Error> for HyprvisorError {
+    fn from(err: bincode::Error) -> Self {
+        HyprvisorError::BincodeError(err)
+    }
+}
+
+impl Display for HyprvisorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HyprvisorError::NoDaemon => write!(f, "No daemon found"),
+            HyprvisorError::JsonError(err) => write!(f, "Json error: {err}"),
+            HyprvisorError::BincodeError(err) => write!(f, "Bincode error: {err}"),
+            HyprvisorError::IpcError => write!(f, "Inter-processes communication error"),
+            HyprvisorError::IoError(err) => write!(f, "IO error: {err}"),
+            HyprvisorError::ParseError => write!(f, "Parse error"),
+            HyprvisorError::InvalidMessage => write!(f, "Invalid message"),
+            HyprvisorError::InvalidResponse => write!(f, "Invalid response"),
+            HyprvisorError::InvalidSubscription => write!(f, "Invalid subscription"),
+            HyprvisorError::NoHyprland => write!(f, "Is Hyprland running?"),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{application::types::ClientInfo, error::HyprvisorError, opts::CommandOpts};
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    types::{ClientInfo, Command},
+};
 
 const MESSAGE_TYPE_LEN: usize = size_of::<MessageType>();
 const MESSAGE_HEADER_LEN: usize = size_of::<usize>();
@@ -9,6 +12,8 @@ pub enum MessageType {
     Command = 0,
     Subscription = 1,
     Response = 2,
+    Query = 3,
+    Shutdown = 4,
 }
 
 impl From<MessageType> for u8 {
@@ -24,6 +29,8 @@ impl TryFrom<u8> for MessageType {
             0 => Ok(MessageType::Command),
             1 => Ok(MessageType::Subscription),
             2 => Ok(MessageType::Response),
+            3 => Ok(MessageType::Query),
+            4 => Ok(MessageType::Shutdown),
             _ => Err(HyprvisorError::ParseError),
         }
     }
@@ -44,25 +51,45 @@ impl HyprvisorMessage {
     pub fn len(&self) -> usize {
         MESSAGE_TYPE_LEN + MESSAGE_HEADER_LEN + self.header
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.header == 0
+    }
+
+    pub fn shutdown(reason: &str) -> HyprvisorMessage {
+        HyprvisorMessage {
+            message_type: MessageType::Shutdown,
+            ..HyprvisorMessage::from(reason)
+        }
+    }
+
+    pub fn query(info: &ClientInfo) -> HyprvisorResult<HyprvisorMessage> {
+        Ok(HyprvisorMessage {
+            message_type: MessageType::Query,
+            ..HyprvisorMessage::try_from(info)?
+        })
+    }
 }
 
-impl From<CommandOpts> for HyprvisorMessage {
-    fn from(opts: CommandOpts) -> HyprvisorMessage {
+impl From<Command> for HyprvisorMessage {
+    fn from(command: Command) -> HyprvisorMessage {
         HyprvisorMessage {
             message_type: MessageType::Command,
             header: size_of::<u8>(),
-            payload: vec![opts.into()],
+            payload: vec![command.into()],
         }
     }
 }
 
-impl From<ClientInfo> for HyprvisorMessage {
-    fn from(info: ClientInfo) -> HyprvisorMessage {
-        HyprvisorMessage {
+impl TryFrom<&ClientInfo> for HyprvisorMessage {
+    type Error = HyprvisorError;
+    fn try_from(info: &ClientInfo) -> HyprvisorResult<HyprvisorMessage> {
+        let payload: Vec<u8> = info.try_into()?;
+        Ok(HyprvisorMessage {
             message_type: MessageType::Subscription,
-            header: ClientInfo::byte_size(),
-            payload: info.into(),
-        }
+            header: payload.len(),
+            payload,
+        })
     }
 }
 
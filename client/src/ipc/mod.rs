@@ -6,3 +6,5 @@ pub use traits::connect_to_socket;
 pub use traits::HyprvisorReadSock;
 pub use traits::HyprvisorRequestResponse;
 pub use traits::HyprvisorWriteSock;
+
+pub const BUFFER_SIZE: usize = 8192;
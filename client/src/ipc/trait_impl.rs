@@ -1,10 +1,8 @@
 use super::{
     message::HyprvisorMessage, HyprvisorReadSock, HyprvisorRequestResponse, HyprvisorWriteSock,
+    BUFFER_SIZE,
 };
-use crate::{
-    error::{HyprvisorError, HyprvisorResult},
-    global::BUFFER_SIZE,
-};
+use crate::error::{HyprvisorError, HyprvisorResult};
 
 use tokio::net::{unix::OwnedWriteHalf, UnixStream};
 
@@ -40,7 +38,7 @@ impl HyprvisorReadSock for UnixStream {
     }
 
     async fn read_message(&self) -> HyprvisorResult<HyprvisorMessage> {
-        let mut buffer = vec![0; *BUFFER_SIZE];
+        let mut buffer = vec![0; BUFFER_SIZE];
         match self.read_bytes(&mut buffer).await {
             Ok(len) if len > 0 => buffer[..len].try_into(),
             Ok(_) => {
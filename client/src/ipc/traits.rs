@@ -4,7 +4,7 @@ use crate::error::{HyprvisorError, HyprvisorResult};
 use std::time::Duration;
 use tokio::{net::UnixStream, time::sleep};
 
-#[allow(unused)]
+#[allow(unused, async_fn_in_trait)]
 pub trait HyprvisorReadSock {
     async fn read_bytes(&self, buffer: &mut [u8]) -> HyprvisorResult<usize>;
     async fn try_read_bytes(&self, buffer: &mut [u8], max_attempt: u8) -> HyprvisorResult<usize>;
@@ -13,6 +13,7 @@ pub trait HyprvisorReadSock {
     async fn try_read_message(&self, max_attempt: u8) -> HyprvisorResult<HyprvisorMessage>;
 }
 
+#[allow(async_fn_in_trait)]
 pub trait HyprvisorWriteSock {
     async fn write_bytes(&self, buffer: &[u8]) -> HyprvisorResult<usize>;
     async fn try_write_bytes(&self, buffer: &[u8], max_attempt: u8) -> HyprvisorResult<usize>;
@@ -25,7 +26,7 @@ pub trait HyprvisorWriteSock {
     ) -> HyprvisorResult<usize>;
 }
 
-#[allow(unused)]
+#[allow(unused, async_fn_in_trait)]
 pub trait HyprvisorRequestResponse {
     async fn send_and_receive_bytes(
         &self,
@@ -0,0 +1,25 @@
+//! Client library for the hyprvisor daemon.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hyprvisor_client::Client;
+//!
+//! # async fn run() -> hyprvisor_client::HyprvisorResult<()> {
+//! let client = Client::from_env()?;
+//! let mut workspaces = Box::pin(client.subscribe_workspaces().await?);
+//!
+//! while let Some(workspaces) = workspaces.next().await {
+//!     println!("{} workspaces", workspaces.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod ipc;
+pub mod socket;
+pub mod types;
+
+pub use client::{Client, Topic};
+pub use error::{HyprvisorError, HyprvisorResult};
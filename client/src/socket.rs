@@ -0,0 +1,26 @@
+use crate::error::{HyprvisorError, HyprvisorResult};
+
+use std::env;
+
+/// Socket path of the daemon serving the current Hyprland session:
+/// `HYPRVISOR_SOCKET` if set, otherwise a per-user path namespaced by the
+/// Hyprland instance signature.
+pub fn default_socket_path() -> HyprvisorResult<String> {
+    if let Ok(path) = env::var("HYPRVISOR_SOCKET") {
+        return Ok(path);
+    }
+
+    let instance_signature =
+        env::var("HYPRLAND_INSTANCE_SIGNATURE").map_err(|_| HyprvisorError::NoHyprland)?;
+
+    let runtime_dir = env::var("XDG_RUNTIME_DIR")
+        .map(|value| format!("{value}/hyprvisor"))
+        .unwrap_or_else(|_| format!("/tmp/hyprvisor-{}", current_uid()));
+
+    Ok(format!("{runtime_dir}/{instance_signature}.sock"))
+}
+
+pub fn current_uid() -> u32 {
+    // SAFETY: getuid() is always successful and has no side effects.
+    unsafe { libc::getuid() }
+}
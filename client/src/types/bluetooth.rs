@@ -0,0 +1,136 @@
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    ipc::message::{HyprvisorMessage, MessageType},
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
+
+/// Bluetooth device address, stored in the same byte order as `bluer::Address`.
+/// JSON carries it as `AA:BB:CC:DD:EE:FF` like `bluer::Address`, IPC as raw bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Address(pub [u8; 6]);
+
+impl Display for Address {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
+        )
+    }
+}
+
+impl FromStr for Address {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("expected an address like AA:BB:CC:DD:EE:FF, got {value}");
+        let mut address = [0; 6];
+        let mut parts = value.split(':');
+
+        for byte in address.iter_mut() {
+            *byte = parts
+                .next()
+                .filter(|part| part.len() == 2)
+                .and_then(|part| u8::from_str_radix(part, 16).ok())
+                .ok_or_else(invalid)?;
+        }
+
+        match parts.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(Address(address)),
+        }
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => self.0.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => String::deserialize(deserializer)?
+                .parse()
+                .map_err(de::Error::custom),
+            false => <[u8; 6]>::deserialize(deserializer).map(Address),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BluetoothDeviceInfo {
+    /// Empty when BlueZ knows no name, clients print a fallback in their language.
+    pub name: String,
+    pub address: Address,
+    /// Device type as reported by BlueZ, e.g. `audio-headset` or `input-mouse`.
+    pub kind: String,
+    /// Icon of the device type, from the icon theme of the daemon.
+    pub icon: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BluetoothInfo {
+    pub powered: bool,
+    pub connected_devices: Vec<BluetoothDeviceInfo>,
+    /// Icon of the adapter state, from the icon theme of the daemon.
+    pub icon: String,
+}
+
+impl TryFrom<HyprvisorMessage> for BluetoothInfo {
+    type Error = HyprvisorError;
+    fn try_from(message: HyprvisorMessage) -> HyprvisorResult<BluetoothInfo> {
+        if !message.is_valid() {
+            return Err(HyprvisorError::InvalidMessage);
+        }
+        bincode::deserialize(&message.payload).map_err(HyprvisorError::BincodeError)
+    }
+}
+
+impl TryFrom<BluetoothInfo> for HyprvisorMessage {
+    type Error = HyprvisorError;
+    fn try_from(bluetooth_info: BluetoothInfo) -> std::result::Result<Self, Self::Error> {
+        let payload: Vec<u8> = bincode::serialize(&bluetooth_info)?;
+        Ok(HyprvisorMessage {
+            message_type: MessageType::Response,
+            header: payload.len(),
+            payload,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_formats() {
+        let address = Address([0xAA, 0xBB, 0xCC, 0x01, 0x02, 0x03]);
+
+        assert_eq!(
+            serde_json::to_string(&address).unwrap(),
+            r#""AA:BB:CC:01:02:03""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Address>(r#""aa:bb:cc:01:02:03""#).unwrap(),
+            address
+        );
+        assert_eq!(
+            bincode::deserialize::<Address>(&bincode::serialize(&address).unwrap()).unwrap(),
+            address
+        );
+
+        assert!("AA:BB:CC:01:02".parse::<Address>().is_err());
+        assert!("AA:BB:CC:01:02:03:04".parse::<Address>().is_err());
+        assert!("AA:BB:CC:01:02:GG".parse::<Address>().is_err());
+    }
+}
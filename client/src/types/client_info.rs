@@ -0,0 +1,102 @@
+use super::{SubscriptionID, SubscriptionOptions};
+use crate::error::HyprvisorError;
+
+use serde::{Deserialize, Serialize};
+
+/// Sent as a JSON object so that both sides can add options: a missing field
+/// takes its default and an unknown one is ignored.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ClientInfo {
+    pub subscription_id: SubscriptionID,
+    #[serde(default)]
+    pub options: SubscriptionOptions,
+}
+
+impl TryFrom<&ClientInfo> for Vec<u8> {
+    type Error = HyprvisorError;
+    fn try_from(client_info: &ClientInfo) -> std::result::Result<Self, Self::Error> {
+        serde_json::to_vec(client_info).map_err(HyprvisorError::JsonError)
+    }
+}
+
+impl TryFrom<&[u8]> for ClientInfo {
+    type Error = HyprvisorError;
+    fn try_from(buffer: &[u8]) -> std::result::Result<Self, Self::Error> {
+        // Clients before the JSON encoding cannot read the current topic data either.
+        match buffer.first() {
+            Some(b'{') => serde_json::from_slice(buffer).map_err(HyprvisorError::JsonError),
+            Some(_) => Err(HyprvisorError::InvalidSubscription),
+            None => Err(HyprvisorError::ParseError),
+        }
+    }
+}
+
+impl ClientInfo {
+    pub fn new(subscription_id: SubscriptionID) -> Self {
+        ClientInfo {
+            subscription_id,
+            options: SubscriptionOptions::default(),
+        }
+    }
+
+    pub fn with_options(subscription_id: SubscriptionID, options: SubscriptionOptions) -> Self {
+        ClientInfo {
+            subscription_id,
+            options,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EllipsisPosition;
+
+    #[test]
+    fn round_trip() {
+        let options = SubscriptionOptions {
+            title_length: Some(20),
+            ellipsis_position: EllipsisPosition::Middle,
+            ..Default::default()
+        };
+        let buffer = Vec::try_from(&ClientInfo::with_options(
+            SubscriptionID::Window,
+            options.clone(),
+        ))
+        .unwrap();
+        let client_info = ClientInfo::try_from(buffer.as_slice()).unwrap();
+
+        assert_eq!(client_info.subscription_id, SubscriptionID::Window);
+        assert_eq!(client_info.options, options);
+    }
+
+    #[test]
+    fn missing_and_unknown_fields() {
+        let client_info = ClientInfo::try_from(
+            br#"{"subscription_id":"Wifi","options":{"ssid_length":8,"new_option":true}}"#
+                .as_slice(),
+        )
+        .unwrap();
+
+        assert_eq!(client_info.subscription_id, SubscriptionID::Wifi);
+        assert_eq!(client_info.options.ssid_length, Some(8));
+        assert_eq!(client_info.options.ellipsis, "...");
+
+        let client_info =
+            ClientInfo::try_from(br#"{"subscription_id":"Bluetooth"}"#.as_slice()).unwrap();
+        assert_eq!(client_info.options, SubscriptionOptions::default());
+    }
+
+    #[test]
+    fn older_clients_are_refused() {
+        // Subscription ID and process ID of a v0.4.4 client.
+        assert!(matches!(
+            ClientInfo::try_from([1, 0x39, 0x30, 0, 0].as_slice()),
+            Err(HyprvisorError::InvalidSubscription)
+        ));
+        assert!(matches!(
+            ClientInfo::try_from([].as_slice()),
+            Err(HyprvisorError::ParseError)
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::HyprvisorError;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Command {
+    Ping = 0,
+    Kill = 1,
+    Status = 2,
+    Reload = 3,
+}
+
+impl From<Command> for u8 {
+    fn from(command: Command) -> Self {
+        command as u8
+    }
+}
+
+impl TryFrom<u8> for Command {
+    type Error = HyprvisorError;
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Command::Ping),
+            1 => Ok(Command::Kill),
+            2 => Ok(Command::Status),
+            3 => Ok(Command::Reload),
+            _ => Err(HyprvisorError::ParseError),
+        }
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Command::Ping => write!(f, "Ping"),
+            Command::Kill => write!(f, "Kill"),
+            Command::Status => write!(f, "Status"),
+            Command::Reload => write!(f, "Reload"),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::{ModuleID, SubscriptionID};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result};
+use tokio::net::UnixStream;
+
+/// Report of the `status` command.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DaemonStatus {
+    pub version: String,
+    /// Seconds since the daemon started.
+    pub uptime: u64,
+    pub hyprland_instance: String,
+    pub socket: String,
+    pub listeners: Vec<ListenerStatus>,
+    pub topics: Vec<TopicStatus>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ListenerStatus {
+    pub module: ModuleID,
+    #[serde(flatten)]
+    pub state: ListenerState,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum ListenerState {
+    /// Enabled, waiting for a subscriber.
+    Idle,
+    Disabled,
+    Starting,
+    Running,
+    Restarting {
+        attempt: usize,
+        max_attempt: usize,
+    },
+    GaveUp,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TopicStatus {
+    pub topic: SubscriptionID,
+    /// RFC 3339 time of the last update, none before the first one.
+    pub last_update: Option<String>,
+    pub subscribers: Vec<SubscriberStatus>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubscriberStatus {
+    pub connection: ConnectionID,
+    #[serde(flatten)]
+    pub peer: PeerInfo,
+}
+
+/// Server-side identifier of an accepted connection.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct ConnectionID(pub u64);
+
+/// Credentials of the peer process, as reported by the kernel (SO_PEERCRED).
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PeerInfo {
+    pub pid: Option<i32>,
+    pub uid: u32,
+}
+
+impl Display for ConnectionID {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl TryFrom<&UnixStream> for PeerInfo {
+    type Error = std::io::Error;
+    fn try_from(stream: &UnixStream) -> std::result::Result<Self, Self::Error> {
+        let cred = stream.peer_cred()?;
+        Ok(PeerInfo {
+            pid: cred.pid(),
+            uid: cred.uid(),
+        })
+    }
+}
+
+impl Display for PeerInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.pid {
+            Some(pid) => write!(f, "pid {pid}, uid {}", self.uid),
+            None => write!(f, "pid unknown, uid {}", self.uid),
+        }
+    }
+}
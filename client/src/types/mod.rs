@@ -0,0 +1,24 @@
+pub mod bluetooth;
+pub mod client_info;
+pub mod command;
+pub mod daemon_status;
+pub mod module_id;
+pub mod subscription_id;
+pub mod subscription_options;
+pub mod wifi;
+pub mod window;
+pub mod workspace;
+
+pub use bluetooth::{Address, BluetoothDeviceInfo, BluetoothInfo};
+pub use client_info::ClientInfo;
+pub use command::Command;
+pub use daemon_status::{
+    ConnectionID, DaemonStatus, ListenerState, ListenerStatus, PeerInfo, SubscriberStatus,
+    TopicStatus,
+};
+pub use module_id::ModuleID;
+pub use subscription_id::SubscriptionID;
+pub use subscription_options::{EllipsisPosition, SubscriptionOptions};
+pub use wifi::{WifiInfo, WifiState};
+pub use window::HyprWindowInfo;
+pub use workspace::HyprWorkspaceInfo;
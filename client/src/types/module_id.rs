@@ -0,0 +1,52 @@
+use super::SubscriptionID;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
+
+/// Listener of the daemon publishing one or more topics.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleID {
+    /// Workspaces and active window.
+    Hyprland,
+    Wifi,
+    Bluetooth,
+}
+
+impl ModuleID {
+    pub const ALL: [ModuleID; 3] = [ModuleID::Hyprland, ModuleID::Wifi, ModuleID::Bluetooth];
+
+    /// Module whose listener publishes a topic.
+    pub fn serving(subscription_id: SubscriptionID) -> Option<ModuleID> {
+        match subscription_id {
+            SubscriptionID::Workspaces | SubscriptionID::Window => Some(ModuleID::Hyprland),
+            SubscriptionID::Wifi => Some(ModuleID::Wifi),
+            SubscriptionID::Bluetooth => Some(ModuleID::Bluetooth),
+            SubscriptionID::Invalid => None,
+        }
+    }
+}
+
+impl Display for ModuleID {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ModuleID::Hyprland => write!(f, "hyprland"),
+            ModuleID::Wifi => write!(f, "wifi"),
+            ModuleID::Bluetooth => write!(f, "bluetooth"),
+        }
+    }
+}
+
+impl FromStr for ModuleID {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        ModuleID::ALL
+            .into_iter()
+            .find(|module| module.to_string() == value)
+            .ok_or_else(|| format!("unknown module: {value}"))
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
+
+/// Options of one subscription. The daemon shapes the data of the topic with
+/// them before sending it, so each client receives exactly what it asked for.
+/// Options that do not apply to the subscribed topic are ignored, and a
+/// missing one takes its default.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct SubscriptionOptions {
+    /// Always list workspaces 1 to N, even the empty ones.
+    pub fix_workspace: Option<u32>,
+    /// Truncate the active window title to this display width.
+    pub title_length: Option<u32>,
+    /// Truncate the SSID to this display width.
+    pub ssid_length: Option<u32>,
+    /// Text replacing what truncation cuts off. It counts in the width.
+    pub ellipsis: String,
+    /// Which part of a long text truncation cuts off.
+    pub ellipsis_position: EllipsisPosition,
+    /// Minimum delay in milliseconds between two updates.
+    pub min_interval: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EllipsisPosition {
+    Start,
+    Middle,
+    #[default]
+    End,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        SubscriptionOptions {
+            fix_workspace: None,
+            title_length: None,
+            ssid_length: None,
+            ellipsis: "...".to_string(),
+            ellipsis_position: EllipsisPosition::default(),
+            min_interval: 0,
+        }
+    }
+}
+
+impl FromStr for EllipsisPosition {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "start" => Ok(EllipsisPosition::Start),
+            "middle" => Ok(EllipsisPosition::Middle),
+            "end" => Ok(EllipsisPosition::End),
+            _ => Err(format!("expected start, middle or end, got {value}")),
+        }
+    }
+}
+
+impl Display for EllipsisPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EllipsisPosition::Start => write!(f, "start"),
+            EllipsisPosition::Middle => write!(f, "middle"),
+            EllipsisPosition::End => write!(f, "end"),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    ipc::message::{HyprvisorMessage, MessageType},
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WifiState {
+    Disabled,
+    Disconnected,
+    Connecting,
+    Connected,
+    #[default]
+    Unknown,
+}
+
+impl From<&str> for WifiState {
+    fn from(value: &str) -> Self {
+        match value {
+            "disabled" => Self::Disabled,
+            "disconnected" => Self::Disconnected,
+            "connecting" => Self::Connecting,
+            "connected" => Self::Connected,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WifiInfo {
+    pub state: WifiState,
+    pub ssid: String,
+    /// Signal quality of the connected network in percent, 0 when not connected.
+    pub signal: u8,
+    /// Icon of the state and signal, from the icon theme of the daemon.
+    pub icon: String,
+}
+
+impl TryFrom<HyprvisorMessage> for WifiInfo {
+    type Error = HyprvisorError;
+    fn try_from(message: HyprvisorMessage) -> HyprvisorResult<WifiInfo> {
+        if !message.is_valid() {
+            return Err(HyprvisorError::InvalidMessage);
+        }
+        bincode::deserialize(&message.payload).map_err(HyprvisorError::BincodeError)
+    }
+}
+
+impl TryFrom<WifiInfo> for HyprvisorMessage {
+    type Error = HyprvisorError;
+    fn try_from(wifi_info: WifiInfo) -> Result<Self, Self::Error> {
+        let payload: Vec<u8> = bincode::serialize(&wifi_info)?;
+        Ok(HyprvisorMessage {
+            message_type: MessageType::Response,
+            header: payload.len(),
+            payload,
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    ipc::message::{HyprvisorMessage, MessageType},
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct HyprWindowInfo {
+    pub class: String,
+    pub title: String,
+}
+
+impl TryFrom<HyprvisorMessage> for HyprWindowInfo {
+    type Error = HyprvisorError;
+    fn try_from(message: HyprvisorMessage) -> HyprvisorResult<HyprWindowInfo> {
+        if !message.is_valid() {
+            return Err(HyprvisorError::InvalidMessage);
+        }
+        bincode::deserialize(&message.payload).map_err(HyprvisorError::BincodeError)
+    }
+}
+
+impl TryFrom<HyprWindowInfo> for HyprvisorMessage {
+    type Error = HyprvisorError;
+    fn try_from(window_info: HyprWindowInfo) -> HyprvisorResult<HyprvisorMessage> {
+        let payload: Vec<u8> = bincode::serialize(&window_info)?;
+        Ok(HyprvisorMessage {
+            message_type: MessageType::Response,
+            header: payload.len(),
+            payload,
+        })
+    }
+}
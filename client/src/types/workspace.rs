@@ -0,0 +1,63 @@
+use crate::{
+    error::{HyprvisorError, HyprvisorResult},
+    ipc::message::{HyprvisorMessage, MessageType},
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct HyprWorkspaceInfo {
+    pub id: u32,
+    pub occupied: bool,
+    pub active: bool,
+    /// A window asked for attention and the workspace was not visited since.
+    pub urgent: bool,
+    /// Icon of the workspace state, from the icon theme of the daemon.
+    pub icon: String,
+}
+
+impl HyprWorkspaceInfo {
+    pub fn default_workspace(id: u32) -> Self {
+        Self {
+            id,
+            occupied: false,
+            active: false,
+            urgent: false,
+            icon: String::new(),
+        }
+    }
+}
+
+impl TryFrom<HyprvisorMessage> for Vec<HyprWorkspaceInfo> {
+    type Error = HyprvisorError;
+    fn try_from(message: HyprvisorMessage) -> HyprvisorResult<Vec<HyprWorkspaceInfo>> {
+        if !message.is_valid() {
+            return Err(HyprvisorError::InvalidMessage);
+        }
+        bincode::deserialize(&message.payload).map_err(HyprvisorError::BincodeError)
+    }
+}
+
+impl TryFrom<Vec<HyprWorkspaceInfo>> for HyprvisorMessage {
+    type Error = HyprvisorError;
+    fn try_from(workspaces: Vec<HyprWorkspaceInfo>) -> HyprvisorResult<HyprvisorMessage> {
+        let payload: Vec<u8> = bincode::serialize(&workspaces)?;
+        Ok(HyprvisorMessage {
+            message_type: MessageType::Response,
+            header: payload.len(),
+            payload,
+        })
+    }
+}
+
+impl TryFrom<&[HyprWorkspaceInfo]> for HyprvisorMessage {
+    type Error = HyprvisorError;
+    fn try_from(workspaces: &[HyprWorkspaceInfo]) -> HyprvisorResult<HyprvisorMessage> {
+        let payload: Vec<u8> = bincode::serialize(workspaces)?;
+        Ok(HyprvisorMessage {
+            message_type: MessageType::Response,
+            header: payload.len(),
+            payload,
+        })
+    }
+}